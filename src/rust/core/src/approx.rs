@@ -0,0 +1,212 @@
+use crate::options::Imputation;
+use crate::parsed::{ParsedDate, Precision};
+use crate::pipeline::days_in_month;
+
+/// Qualifier marking a date as approximate, e.g. "circa 1985" or "mid-May 2020"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Qualifier {
+    /// Around the date given, which keeps its whole period
+    Circa,
+    /// First third of the period given
+    Early,
+    /// Middle third of the period given
+    Mid,
+    /// Last third of the period given
+    Late,
+}
+
+impl Qualifier {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Qualifier::Circa => "circa",
+            Qualifier::Early => "early",
+            Qualifier::Mid => "mid",
+            Qualifier::Late => "late",
+        }
+    }
+
+    /// Which third of the period the qualifier narrows to, `None` for circa
+    pub fn third(self) -> Option<usize> {
+        match self {
+            Qualifier::Circa => None,
+            Qualifier::Early => Some(0),
+            Qualifier::Mid => Some(1),
+            Qualifier::Late => Some(2),
+        }
+    }
+}
+
+/// Qualifiers in the supported languages, in lower case
+static QUALIFIER_NAMES: &[(Qualifier, &[&str])] = &[
+    (
+        Qualifier::Circa,
+        &[
+            "circa", "ca.", "ca", "c.", "approx.", "approx", "approximately", "about", "around",
+            "~", "vers", "environ", "etwa", "ungefähr", "um", "alrededor de", "hacia", "aprox.",
+            "cerca de", "por volta de",
+        ],
+    ),
+    (
+        Qualifier::Early,
+        &[
+            "early", "début", "debut", "anfang", "principios de", "inicios de", "início de",
+            "inicio de",
+        ],
+    ),
+    (
+        Qualifier::Mid,
+        &[
+            "mid", "middle of", "mi", "milieu", "mitte", "mediados de", "meados de",
+        ],
+    ),
+    (
+        Qualifier::Late,
+        &[
+            "late", "fin", "ende", "finales de", "fines de", "final de", "fim de",
+        ],
+    ),
+];
+
+/// Words joining a qualifier to the date, as in "fin de 2020" or
+/// "début des années 90"
+static CONNECTORS: &[&str] = &["of ", "de ", "des ", "du ", "del "];
+
+/// Split a leading qualifier from a date
+///
+/// Returns `None` when `date` has no qualifier. Qualifiers are followed by
+/// whitespace or a hyphen, as in "mid-May 2020", except for "~" and
+/// abbreviations ending in a full stop.
+pub fn split_qualifier(date: &str) -> Option<(Qualifier, &str)> {
+    let (qualifier, len) = QUALIFIER_NAMES
+        .iter()
+        .flat_map(|(qualifier, names)| names.iter().map(move |name| (*qualifier, *name)))
+        .filter(|(_, name)| {
+            let Some(head) = date.get(..name.len()) else {
+                return false;
+            };
+            let rest = &date[name.len()..];
+            head.to_lowercase() == *name
+                && (name.ends_with(['~', '.'])
+                    || rest.starts_with(|c: char| c.is_whitespace() || c == '-'))
+        })
+        .map(|(qualifier, name)| (qualifier, name.len()))
+        .max_by_key(|&(_, len)| len)?;
+
+    let mut rest = date[len..].trim_start_matches(|c: char| c.is_whitespace() || c == '-');
+    if let Some(connector) = CONNECTORS.iter().find(|c| {
+        rest.get(..c.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(c))
+    }) {
+        rest = rest[connector.len()..].trim_start();
+    }
+    if rest.is_empty() {
+        return None;
+    }
+    Some((qualifier, rest))
+}
+
+/// Whether `word` on its own is a qualifier, such as "mid" in "mid-May 2020"
+pub(crate) fn is_qualifier(word: &str) -> bool {
+    let word = word.to_lowercase();
+    QUALIFIER_NAMES
+        .iter()
+        .any(|(_, names)| names.contains(&word.as_str()))
+}
+
+/// Value at the position `impute` picks between `first` and `last`
+fn pick(first: i32, last: i32, impute: Imputation) -> i32 {
+    match impute {
+        Imputation::Middle => (first + last) / 2,
+        Imputation::Last => last,
+        _ => first,
+    }
+}
+
+/// Mark `parsed` with `qualifier` and move its imputed components into the
+/// part of its period the qualifier narrows to
+///
+/// "late 1990s" picks a year from 1997 to 1999 and "mid-May 2020" a day
+/// from the 11th to the 20th, following the imputation policies in the same
+/// way as for a whole period. Given components are never changed.
+pub(crate) fn qualify(
+    parsed: ParsedDate,
+    qualifier: Qualifier,
+    day_impute: Imputation,
+    month_impute: Imputation,
+) -> ParsedDate {
+    let parsed = ParsedDate {
+        qualifier: Some(qualifier),
+        ..parsed
+    };
+    if qualifier.third().is_none() {
+        return parsed;
+    }
+    let (lower, upper) = parsed.interval();
+    let (year, month) = match parsed.precision() {
+        Precision::Century | Precision::Decade => {
+            (pick(lower.year, upper.year, month_impute), parsed.month)
+        }
+        Precision::Year | Precision::Season | Precision::Quarter => {
+            (lower.year, pick(lower.month, upper.month, month_impute))
+        }
+        Precision::Month => {
+            let day = pick(lower.day, upper.day, day_impute);
+            return ParsedDate { day, ..parsed };
+        }
+        Precision::Week | Precision::Day => return parsed,
+    };
+    let day = match day_impute.day(year, month) {
+        // Keep the day of a date only moved to another year, such as the
+        // 30 June of a year imputed with "middle"
+        Some(_) if month == parsed.month && day_impute != Imputation::Last => parsed.day,
+        Some(day) => day,
+        None => parsed.day,
+    };
+    ParsedDate {
+        year,
+        month,
+        day: day.min(days_in_month(year, month)),
+        ..parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_qualifier() {
+        assert_eq!(
+            split_qualifier("circa 1985"),
+            Some((Qualifier::Circa, "1985"))
+        );
+        assert_eq!(split_qualifier("ca. 1985"), Some((Qualifier::Circa, "1985")));
+        assert_eq!(split_qualifier("~1985"), Some((Qualifier::Circa, "1985")));
+        assert_eq!(
+            split_qualifier("Approx. March 2020"),
+            Some((Qualifier::Circa, "March 2020"))
+        );
+        assert_eq!(
+            split_qualifier("alrededor de 1985"),
+            Some((Qualifier::Circa, "1985"))
+        );
+        assert_eq!(
+            split_qualifier("mid-May 2020"),
+            Some((Qualifier::Mid, "May 2020"))
+        );
+        assert_eq!(
+            split_qualifier("early 2000s"),
+            Some((Qualifier::Early, "2000s"))
+        );
+        assert_eq!(
+            split_qualifier("début des années 90"),
+            Some((Qualifier::Early, "années 90"))
+        );
+        assert_eq!(split_qualifier("Ende 2019"), Some((Qualifier::Late, "2019")));
+
+        assert_eq!(split_qualifier("March 2020"), None);
+        assert_eq!(split_qualifier("Mai 2020"), None);
+        assert_eq!(split_qualifier("circa"), None);
+        assert_eq!(split_qualifier("2020-03-15"), None);
+    }
+}
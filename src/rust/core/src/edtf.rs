@@ -0,0 +1,295 @@
+use chrono::NaiveDate;
+use regex::Regex;
+use std::sync::OnceLock;
+
+use crate::approx::Qualifier;
+use crate::error::{Component, DateFixError};
+use crate::options::{Hemisphere, Imputation};
+use crate::parsed::{DateSource, ParsedDate};
+use crate::pipeline::{
+    check_output, days_in_month, handle_season_date, impute_year_only, impute_year_span,
+};
+
+/// Open end of an EDTF interval or set, as in "1985-04-12/.."
+pub const OPEN_END: &str = "..";
+
+/// Seasons of EDTF level 1, numbered 21 to 24 in place of a month
+static EDTF_SEASONS: &[&str] = &["spring", "summer", "autumn", "winter"];
+
+/// An EDTF level 0 or 1 date: "2004-06-11", "2004-06-XX", "201X", "2001-21",
+/// "-0044-03-15" or "Y170002", optionally followed by "?", "~" or "%"
+fn edtf_regex() -> &'static Regex {
+    static EDTF_REGEX: OnceLock<Regex> = OnceLock::new();
+    EDTF_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?i)^(?P<year>Y-?\d{5,}|-?\d{4}|\d{3}X|\d{2}XX)(?:-(?P<month>\d{2}|XX)(?:-(?P<day>\d{2}|XX))?)?(?P<marker>[?~%])?$",
+        )
+        .unwrap()
+    })
+}
+
+/// One side of an EDTF interval, which may carry a time of day
+fn edtf_side_regex() -> &'static Regex {
+    static EDTF_SIDE_REGEX: OnceLock<Regex> = OnceLock::new();
+    EDTF_SIDE_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?i)^(?:Y-?\d{5,}|-?[\dX]{4})(?:-[\dX]{2}){0,2}[?~%]?(?:T\d{2}:\d{2}:\d{2}(?:Z|[+-]\d{2}(?::\d{2})?)?)?$",
+        )
+        .unwrap()
+    })
+}
+
+/// Handle dates written in the Extended Date/Time Format (EDTF)
+///
+/// Only dates using EDTF syntax are taken: unspecified digits ("2004-06-XX",
+/// "201X"), seasons ("2001-21"), years before 1 or after 9999, and the
+/// uncertain ("?"), approximate ("~") or both ("%") markers. Plain ISO 8601
+/// dates are left to the other stages. Unspecified components are imputed
+/// like missing ones, with "201X" treated as a decade and "20XX" as a
+/// century, and seasons are placed using `hemisphere`.
+pub(crate) fn handle_edtf_date(
+    date: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    hemisphere: Hemisphere,
+    strict: bool,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    let Some(caps) = edtf_regex().captures(date) else {
+        return Ok(None);
+    };
+    let year = caps["year"].to_ascii_uppercase();
+    let month = caps.name("month").map(|m| m.as_str().to_ascii_uppercase());
+    let day = caps.name("day").map(|d| d.as_str().to_ascii_uppercase());
+    let marker = caps.name("marker").map(|m| m.as_str());
+    let unspecified = |component: &Option<String>| component.as_deref() == Some("XX");
+    let season = match (&month, &day) {
+        (Some(month), None) => month
+            .parse::<usize>()
+            .ok()
+            .filter(|m| (21..=24).contains(m)),
+        _ => None,
+    };
+    let edtf_syntax = marker.is_some()
+        || year.contains('X')
+        || year.starts_with(['Y', '-'])
+        || unspecified(&month)
+        || unspecified(&day)
+        || season.is_some();
+    if !edtf_syntax {
+        return Ok(None);
+    }
+
+    let input = date.to_string();
+    let unparseable = || DateFixError::Unparseable {
+        input: input.clone(),
+    };
+    // Components after an unspecified one must be unspecified too
+    let specified = |component: &Option<String>| component.is_some() && !unspecified(component);
+    if (year.contains('X') && (specified(&month) || specified(&day)))
+        || (unspecified(&month) && specified(&day))
+    {
+        return Err(unparseable());
+    }
+
+    let mut parsed = if let Some(century) = year.strip_suffix("XX") {
+        let first = century.parse::<i32>().unwrap() * 100;
+        impute_year_span(
+            first,
+            first + 99,
+            DateSource::Century,
+            &input,
+            day_impute,
+            month_impute,
+            strict,
+        )?
+    } else if let Some(decade) = year.strip_suffix('X') {
+        let first = decade.parse::<i32>().unwrap() * 10;
+        impute_year_span(
+            first,
+            first + 9,
+            DateSource::Decade,
+            &input,
+            day_impute,
+            month_impute,
+            strict,
+        )?
+    } else {
+        let year_value = year
+            .trim_start_matches('Y')
+            .parse::<i32>()
+            .ok()
+            .filter(|&y| NaiveDate::from_ymd_opt(y, 1, 1).is_some())
+            .ok_or_else(|| DateFixError::InvalidComponent {
+                component: Component::Year,
+                value: year.clone(),
+                input: input.clone(),
+            })?;
+        edtf_date(
+            year_value,
+            month.filter(|_| season.is_none()),
+            day,
+            &input,
+            day_impute,
+            month_impute,
+            strict,
+        )?
+    };
+
+    if let Some(season) = season {
+        if !(0..=9999).contains(&parsed.year) {
+            return Err(unparseable());
+        }
+        let name = format!("{} {:04}", EDTF_SEASONS[season - 21], parsed.year);
+        parsed = handle_season_date(&name, day_impute, month_impute, hemisphere)?
+            .ok_or_else(unparseable)?;
+    }
+
+    match marker {
+        Some("~") => parsed.qualifier = Some(Qualifier::Circa),
+        Some("?") => parsed.uncertain = true,
+        Some("%") => {
+            parsed.qualifier = Some(Qualifier::Circa);
+            parsed.uncertain = true;
+        }
+        _ => {}
+    }
+    Ok(Some(parsed))
+}
+
+/// A date from an EDTF year and its month and day when specified, imputing
+/// the missing or unspecified ones
+fn edtf_date(
+    year: i32,
+    month: Option<String>,
+    day: Option<String>,
+    input: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    strict: bool,
+) -> Result<ParsedDate, DateFixError> {
+    let value = |component: Option<String>| component.and_then(|c| c.parse::<i32>().ok());
+    let (month, day) = (value(month), value(day));
+    let Some(month) = month else {
+        let parsed = impute_year_only(year, input, day_impute, month_impute, strict)?;
+        return Ok(ParsedDate {
+            source: DateSource::Edtf,
+            ..parsed
+        });
+    };
+
+    let (_, month, _) = check_output(None, Some(month), Some(year), strict)?;
+    let month = month.unwrap();
+    let parsed = match day {
+        Some(day) => {
+            let (adjusted, _, _) = check_output(Some(day), Some(month), Some(year), strict)?;
+            let adjusted = adjusted.unwrap();
+            let mut parsed = ParsedDate::new(year, month, adjusted, DateSource::Edtf);
+            parsed.day_clamped = adjusted != day;
+            parsed.format = Some("ymd");
+            parsed
+        }
+        None => {
+            let input = input.to_string();
+            let day = match day_impute {
+                Imputation::Na => return Err(DateFixError::NaImputed { input }),
+                Imputation::Error => return Err(DateFixError::MissingDay { input }),
+                day_impute => day_impute.day(year, month).unwrap(),
+            };
+            let adjusted = day.min(days_in_month(year, month));
+            let mut parsed = ParsedDate::new(year, month, adjusted, DateSource::Edtf);
+            parsed.day_imputed = true;
+            parsed.day_clamped = adjusted != day;
+            parsed.format = Some("ym");
+            parsed
+        }
+    };
+    Ok(parsed)
+}
+
+/// Split an EDTF interval such as "1985-04/1985-06" or a set such as
+/// "[1667,1668,1670..1672]" into its first and last dates
+///
+/// Returns `None` for anything else. An open end ("1985/..",
+/// "[..1760-12-03]") is returned as `OPEN_END` and an unknown end
+/// ("1985/") as an empty string.
+pub fn split_edtf_interval(date: &str) -> Option<(String, String)> {
+    let is_side =
+        |side: &str| side.is_empty() || side == OPEN_END || edtf_side_regex().is_match(side);
+    let date = date.trim();
+
+    let set = date
+        .strip_prefix('[')
+        .and_then(|d| d.strip_suffix(']'))
+        .or_else(|| date.strip_prefix('{').and_then(|d| d.strip_suffix('}')));
+    let (start, end) = match set {
+        Some(elements) => {
+            let first = elements.split(',').next()?.trim();
+            let last = elements.rsplit(',').next()?.trim();
+            let start = if first.starts_with(OPEN_END) {
+                OPEN_END
+            } else {
+                first.split(OPEN_END).next()?
+            };
+            let end = if last.ends_with(OPEN_END) {
+                OPEN_END
+            } else {
+                last.rsplit(OPEN_END).next()?
+            };
+            (start, end)
+        }
+        None => {
+            let (start, end) = date.split_once('/')?;
+            if end.contains('/') || (start.is_empty() && end.is_empty()) {
+                return None;
+            }
+            (start, end)
+        }
+    };
+    if !is_side(start) || !is_side(end) {
+        return None;
+    }
+    Some((start.to_string(), end.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_edtf_interval() {
+        let pair = |start: &str, end: &str| Some((start.to_string(), end.to_string()));
+
+        assert_eq!(split_edtf_interval("1964/2008"), pair("1964", "2008"));
+        assert_eq!(
+            split_edtf_interval("1985-04/1985-06"),
+            pair("1985-04", "1985-06")
+        );
+        assert_eq!(
+            split_edtf_interval("2004-02-01/2005-02-08~"),
+            pair("2004-02-01", "2005-02-08~")
+        );
+        assert_eq!(
+            split_edtf_interval("1985-04-12/.."),
+            pair("1985-04-12", "..")
+        );
+        assert_eq!(split_edtf_interval("/1985-04-12"), pair("", "1985-04-12"));
+        assert_eq!(
+            split_edtf_interval("[1667,1668,1670..1672]"),
+            pair("1667", "1672")
+        );
+        assert_eq!(
+            split_edtf_interval("[..1760-12-03]"),
+            pair("..", "1760-12-03")
+        );
+        assert_eq!(
+            split_edtf_interval("{1960,1961-12}"),
+            pair("1960", "1961-12")
+        );
+
+        assert_eq!(split_edtf_interval("15/03/2020"), None);
+        assert_eq!(split_edtf_interval("03/2020"), None);
+        assert_eq!(split_edtf_interval("winter 2018/19"), None);
+        assert_eq!(split_edtf_interval("1985-04-12"), None);
+        assert_eq!(split_edtf_interval("/"), None);
+    }
+}
@@ -0,0 +1,223 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::translations::*;
+
+/// Date component an error refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    Day,
+    Month,
+    Year,
+    Week,
+    Hour,
+    Minute,
+    Second,
+    Offset,
+}
+
+impl Component {
+    fn name(self) -> &'static str {
+        match self {
+            Component::Day => "day",
+            Component::Month => "month",
+            Component::Year => "year",
+            Component::Week => "week",
+            Component::Hour => "hour",
+            Component::Minute => "minute",
+            Component::Second => "second",
+            Component::Offset => "time zone offset",
+        }
+    }
+}
+
+/// Errors raised while tidying a single date
+///
+/// Every variant carries the original input so callers can report it without
+/// re-threading the raw string. Messages are only translated when converted
+/// into an R error at the extendr boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateFixError {
+    /// Day of the month outside 1-31
+    DayOutOfRange { day: i32, input: String },
+    /// Month outside 1-12
+    MonthOutOfRange { month: i32, input: String },
+    /// Day missing and no day imputation value given
+    MissingDay { input: String },
+    /// Month missing and no month imputation value given
+    MissingMonth { input: String },
+    /// Numeric component too long to be a year
+    YearTooLong { year: String, input: String },
+    /// Two digit year in strict mode, where the century is not guessed
+    TwoDigitYear { year: String, input: String },
+    /// More than three date components
+    TooManyComponents { components: usize, input: String },
+    /// Only one component and it is not a year or a serial number
+    InsufficientComponents { input: String },
+    /// Two components, neither of which is a four digit year
+    AmbiguousComponents { input: String },
+    /// Component which is not a number after month name conversion
+    InvalidComponent {
+        component: Component,
+        value: String,
+        input: String,
+    },
+    /// Excel serial or Unix day count outside the supported date range
+    InvalidSerial {
+        value: String,
+        excel: bool,
+        input: String,
+    },
+    /// Component too long or malformed to be any part of a date
+    Unparseable { input: String },
+    /// `format` is not `"dmy"`, `"mdy"` or `"auto"`
+    InvalidFormat { format: String },
+    /// Imputation value which is not a day of the month or a month
+    InvalidImputation { component: Component, value: i32 },
+    /// Date is not a character object
+    NotCharacter,
+    /// Day or month imputation is NA so the date resolves to NA
+    NaImputed { input: String },
+    /// Blank or a missing-value token such as "N/A"
+    Missing { input: String },
+    /// Date range whose end is before its start, e.g. "2021-2019"
+    ReversedRange { input: String },
+}
+
+impl DateFixError {
+    /// Replace the recorded input with the original, untouched date string
+    pub fn with_input(mut self, original: &str) -> Self {
+        match &mut self {
+            DateFixError::DayOutOfRange { input, .. }
+            | DateFixError::MonthOutOfRange { input, .. }
+            | DateFixError::MissingDay { input }
+            | DateFixError::MissingMonth { input }
+            | DateFixError::YearTooLong { input, .. }
+            | DateFixError::TwoDigitYear { input, .. }
+            | DateFixError::TooManyComponents { input, .. }
+            | DateFixError::InsufficientComponents { input }
+            | DateFixError::AmbiguousComponents { input }
+            | DateFixError::InvalidComponent { input, .. }
+            | DateFixError::InvalidSerial { input, .. }
+            | DateFixError::Unparseable { input }
+            | DateFixError::NaImputed { input }
+            | DateFixError::Missing { input }
+            | DateFixError::ReversedRange { input } => *input = original.to_string(),
+            DateFixError::InvalidFormat { .. }
+            | DateFixError::InvalidImputation { .. }
+            | DateFixError::NotCharacter => {}
+        }
+        self
+    }
+
+    /// Stable snake_case identifier for the error kind, used in diagnostics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DateFixError::DayOutOfRange { .. } => "day_out_of_range",
+            DateFixError::MonthOutOfRange { .. } => "month_out_of_range",
+            DateFixError::MissingDay { .. } => "missing_day",
+            DateFixError::MissingMonth { .. } => "missing_month",
+            DateFixError::YearTooLong { .. } => "year_too_long",
+            DateFixError::TwoDigitYear { .. } => "two_digit_year",
+            DateFixError::TooManyComponents { .. } => "too_many_components",
+            DateFixError::InsufficientComponents { .. } => "insufficient_components",
+            DateFixError::AmbiguousComponents { .. } => "ambiguous_components",
+            DateFixError::InvalidComponent { .. } => "invalid_component",
+            DateFixError::InvalidSerial { .. } => "invalid_serial",
+            DateFixError::Unparseable { .. } => "unparseable",
+            DateFixError::InvalidFormat { .. } => "invalid_format",
+            DateFixError::InvalidImputation { .. } => "invalid_imputation",
+            DateFixError::NotCharacter => "not_character",
+            DateFixError::NaImputed { .. } => "na_imputed",
+            DateFixError::Missing { .. } => "missing",
+            DateFixError::ReversedRange { .. } => "reversed_range",
+        }
+    }
+
+    /// Untranslated message, matching the msgids used by the translation tables
+    fn msgid(&self) -> Cow<'static, str> {
+        match self {
+            DateFixError::DayOutOfRange { .. } => Cow::Borrowed("Day not in expected range\n"),
+            DateFixError::MonthOutOfRange { .. } => Cow::Borrowed("Month not in expected range\n"),
+            DateFixError::MissingDay { .. } => {
+                Cow::Borrowed("Missing day with no imputation value given \n")
+            }
+            DateFixError::MissingMonth { .. } => {
+                Cow::Borrowed("Missing month with no imputation value given \n")
+            }
+            DateFixError::YearTooLong { .. }
+            | DateFixError::TooManyComponents { .. }
+            | DateFixError::Unparseable { .. } => Cow::Borrowed("unable to tidy a date"),
+            DateFixError::TwoDigitYear { .. } => {
+                Cow::Borrowed("Two digit year not allowed in strict mode")
+            }
+            DateFixError::InsufficientComponents { .. } => {
+                Cow::Borrowed("Insufficient date components")
+            }
+            DateFixError::AmbiguousComponents { .. } => {
+                Cow::Borrowed("Unable to determine date format")
+            }
+            DateFixError::InvalidComponent { component, .. } => {
+                Cow::Owned(format!("Invalid {}", component.name()))
+            }
+            DateFixError::InvalidSerial { excel: true, .. } => Cow::Borrowed("Invalid Excel date"),
+            DateFixError::InvalidSerial { excel: false, .. } => {
+                Cow::Borrowed("Invalid Unix timestamp date")
+            }
+            DateFixError::InvalidFormat { .. } => {
+                Cow::Borrowed("format should be one of 'dmy', 'mdy' or 'auto'")
+            }
+            DateFixError::InvalidImputation {
+                component: Component::Day,
+                ..
+            } => Cow::Borrowed("day.impute should be an integer between 1 and 31\n"),
+            DateFixError::InvalidImputation { .. } => {
+                Cow::Borrowed("month.impute should be an integer between 1 and 12")
+            }
+            DateFixError::NotCharacter => Cow::Borrowed("date should be a character"),
+            DateFixError::NaImputed { .. } => Cow::Borrowed("NA imputed"),
+            DateFixError::Missing { .. } => Cow::Borrowed("Date is missing"),
+            DateFixError::ReversedRange { .. } => {
+                Cow::Borrowed("Date range ends before it starts")
+            }
+        }
+    }
+
+    /// Translated message for the current locale
+    pub fn message(&self) -> String {
+        match self {
+            DateFixError::DayOutOfRange { .. } => day_not_in_range(),
+            DateFixError::MonthOutOfRange { .. } => format!("{}\n", month_not_in_range()),
+            DateFixError::MissingDay { .. } => missing_day_no_imputation(),
+            DateFixError::MissingMonth { .. } => missing_month_no_imputation(),
+            DateFixError::YearTooLong { .. }
+            | DateFixError::TooManyComponents { .. }
+            | DateFixError::Unparseable { .. } => unable_to_tidy_date(),
+            DateFixError::InvalidFormat { .. } => format_should_be_dmy_mdy_or_auto(),
+            DateFixError::NotCharacter => date_should_be_character(),
+            DateFixError::TwoDigitYear { .. } => two_digit_year_strict(),
+            DateFixError::NaImputed { input } => {
+                format!("{} {} {}", na_imputed_date(), input, close_paren())
+            }
+            _ => tr(&self.msgid()),
+        }
+    }
+}
+
+impl fmt::Display for DateFixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateFixError::NaImputed { input } => write!(f, "NA imputed (date: {})", input),
+            _ => f.write_str(self.msgid().trim_end()),
+        }
+    }
+}
+
+impl std::error::Error for DateFixError {}
+
+#[cfg(feature = "extendr")]
+impl From<DateFixError> for extendr_api::Error {
+    fn from(e: DateFixError) -> Self {
+        extendr_api::Error::Other(e.message())
+    }
+}
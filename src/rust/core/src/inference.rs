@@ -0,0 +1,74 @@
+use crate::error::DateFixError;
+use crate::parsed::ParsedDate;
+
+/// What a single date says about the day/month order of its column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderEvidence {
+    /// Only valid when read as day-month-year
+    DmyOnly,
+    /// Only valid when read as month-day-year
+    MdyOnly,
+    /// Valid both ways with different results, e.g. "03/04/2020"
+    Ambiguous,
+    /// Same result either way, or invalid either way
+    Neutral,
+}
+
+impl OrderEvidence {
+    /// Compare the outcomes of parsing a date as DMY and as MDY
+    ///
+    /// NA imputation does not depend on the order so it never counts as a
+    /// failure.
+    pub fn from_results(
+        dmy: &Result<Option<ParsedDate>, DateFixError>,
+        mdy: &Result<Option<ParsedDate>, DateFixError>,
+    ) -> Self {
+        let failed = |r: &Result<Option<ParsedDate>, DateFixError>| match r {
+            Err(DateFixError::NaImputed { .. }) => false,
+            r => r.is_err(),
+        };
+        match (dmy, mdy) {
+            (Ok(Some(d)), Ok(Some(m))) if (d.year, d.month, d.day) != (m.year, m.month, m.day) => {
+                OrderEvidence::Ambiguous
+            }
+            (Ok(Some(_)), m) if failed(m) => OrderEvidence::DmyOnly,
+            (d, Ok(Some(_))) if failed(d) => OrderEvidence::MdyOnly,
+            _ => OrderEvidence::Neutral,
+        }
+    }
+}
+
+/// Day/month order inferred for a column parsed with `format = "auto"`
+///
+/// Counts are in rows, so repeated dates weigh as often as they occur.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatInference {
+    pub dmy_only: usize,
+    pub mdy_only: usize,
+    pub ambiguous: usize,
+}
+
+impl FormatInference {
+    pub fn add(&mut self, evidence: OrderEvidence, rows: usize) {
+        match evidence {
+            OrderEvidence::DmyOnly => self.dmy_only += rows,
+            OrderEvidence::MdyOnly => self.mdy_only += rows,
+            OrderEvidence::Ambiguous => self.ambiguous += rows,
+            OrderEvidence::Neutral => {}
+        }
+    }
+
+    /// Order used for ambiguous dates: the one with more evidence, DMY on a tie
+    pub fn format(&self) -> &'static str {
+        if self.mdy_only > self.dmy_only {
+            "mdy"
+        } else {
+            "dmy"
+        }
+    }
+
+    /// Rows which are only valid in the order that was not chosen
+    pub fn conflicts(&self) -> usize {
+        self.dmy_only.min(self.mdy_only)
+    }
+}
@@ -0,0 +1,138 @@
+pub mod translations;
+mod optimizations;
+mod error;
+mod parsed;
+mod missing;
+mod inference;
+mod trace;
+mod options;
+mod pipeline;
+mod range;
+mod time;
+mod approx;
+mod edtf;
+
+pub use approx::{split_qualifier, Qualifier};
+pub use edtf::{split_edtf_interval, OPEN_END};
+pub use error::{Component, DateFixError};
+pub use inference::{FormatInference, OrderEvidence};
+pub use missing::{MissingTokens, DEFAULT_MISSING_TOKENS};
+pub use optimizations::cleaning_steps;
+pub use options::{DateFormat, Hemisphere, Imputation, ParseOptions};
+pub use parsed::{DateSource, ParsedDate, Precision};
+pub use pipeline::{ambiguous_candidates, parse_date_pipeline, trace_date_pipeline};
+pub use range::{parse_range_pipeline, range_interval, split_range};
+pub use time::{split_time, TimeOfDay};
+pub use trace::{show_components, Trace, TraceStep};
+
+/// Tidy a single date
+///
+/// Blank dates and the missing-value tokens of `options` give
+/// `DateFixError::Missing`, and errors always name `date` as their input.
+///
+/// ```
+/// use datefixr_core::{parse, ParseOptions};
+///
+/// let date = parse("3. Oktober 1990", &ParseOptions::new()).unwrap();
+/// assert_eq!((date.year, date.month, date.day), (1990, 10, 3));
+/// ```
+pub fn parse(date: &str, options: &ParseOptions) -> Result<ParsedDate, DateFixError> {
+    if options.missing().is_missing(date) {
+        return Err(DateFixError::Missing {
+            input: date.to_string(),
+        });
+    }
+
+    match parse_date_pipeline(date, options) {
+        Ok(Some(parsed)) => Ok(parsed),
+        Ok(None) => Err(DateFixError::Missing {
+            input: date.to_string(),
+        }),
+        Err(e) => Err(e.with_input(date)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let options = ParseOptions::new();
+
+        let date = parse("02/05/92", &options).unwrap();
+        assert_eq!((date.year, date.month, date.day), (1992, 5, 2));
+
+        let date = parse("1994", &options).unwrap();
+        assert_eq!((date.year, date.month, date.day), (1994, 7, 1));
+
+        let date = parse("07/15/11", &ParseOptions::new().format(DateFormat::Mdy)).unwrap();
+        assert_eq!((date.year, date.month, date.day), (2011, 7, 15));
+
+        let auto = ParseOptions::new().format(DateFormat::Auto);
+        let date = parse("12/25/2023", &auto).unwrap();
+        assert_eq!((date.year, date.month, date.day), (2023, 12, 25));
+        let date = parse("01/02/2023", &auto).unwrap();
+        assert_eq!((date.year, date.month, date.day), (2023, 2, 1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let options = ParseOptions::new();
+
+        assert_eq!(
+            parse(" N/A ", &options),
+            Err(DateFixError::Missing {
+                input: " N/A ".to_string()
+            })
+        );
+        assert_eq!(parse("", &options).unwrap_err().kind(), "missing");
+        let pending = ParseOptions::new().missing_tokens(MissingTokens::new(["Pending"]));
+        assert_eq!(parse("pending ", &pending).unwrap_err().kind(), "missing");
+        assert_ne!(parse("N/A", &pending).unwrap_err().kind(), "missing");
+        assert_eq!(
+            parse("32-01-2023", &options),
+            Err(DateFixError::DayOutOfRange {
+                day: 32,
+                input: "32-01-2023".to_string()
+            })
+        );
+
+        // Day counts past the dates chrono can represent
+        assert_eq!(
+            parse("99999999999", &options),
+            Err(DateFixError::InvalidSerial {
+                value: "99999999999".to_string(),
+                excel: false,
+                input: "99999999999".to_string()
+            })
+        );
+        let excel = ParseOptions::new().excel(true);
+        assert_eq!(parse("99999999999", &excel).unwrap_err().kind(), "invalid_serial");
+        assert_eq!(
+            parse("99999999999", &excel).unwrap_err().to_string(),
+            "Invalid Excel date"
+        );
+
+        let strict = ParseOptions::new().strict(true);
+        assert_eq!(parse("31/02/2021", &strict).unwrap_err().kind(), "day_out_of_range");
+        assert_eq!(parse("2021", &strict).unwrap_err().kind(), "missing_month");
+
+        let no_day = ParseOptions::new().day_impute(Imputation::Error);
+        assert_eq!(parse("03/2021", &no_day).unwrap_err().kind(), "missing_day");
+        let na_day = ParseOptions::new().day_impute(Imputation::Na);
+        assert_eq!(parse("03/2021", &na_day).unwrap_err().kind(), "na_imputed");
+
+        let bad_month = ParseOptions::new().month_impute(Imputation::Value(13));
+        assert_eq!(
+            parse("2021", &bad_month),
+            Err(DateFixError::InvalidImputation {
+                component: Component::Month,
+                value: 13
+            })
+        );
+        let bad_day = ParseOptions::new().day_impute(Imputation::Value(40));
+        assert_eq!(parse("03/2021", &bad_day).unwrap_err().kind(), "invalid_imputation");
+        assert_eq!(parse("15/03/2021", &bad_day).unwrap_err().kind(), "invalid_imputation");
+    }
+}
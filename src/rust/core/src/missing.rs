@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Tokens treated as missing when the caller does not supply their own.
+/// Compared case-insensitively after trimming whitespace
+pub const DEFAULT_MISSING_TOKENS: &[&str] = &[
+    // English and language-neutral
+    "na",
+    "n/a",
+    "n.a.",
+    "nan",
+    "null",
+    "none",
+    "?",
+    "-",
+    "--",
+    "unknown",
+    "unk",
+    "nd",
+    "n.d.",
+    "not recorded",
+    "not known",
+    "missing",
+    // French
+    "inconnu",
+    "inconnue",
+    "non renseigné",
+    // German
+    "unbekannt",
+    "k.a.",
+    "keine angabe",
+    // Spanish
+    "desconocido",
+    "desconocida",
+    "sin dato",
+    "s/d",
+    // Portuguese
+    "desconhecido",
+    "desconhecida",
+    "sem informação",
+    // Russian
+    "неизвестно",
+    "нет данных",
+    // Czech and Slovak
+    "neznámý",
+    "neznámy",
+    // Indonesian
+    "tidak diketahui",
+];
+
+static DEFAULT: OnceLock<MissingTokens> = OnceLock::new();
+
+/// Set of strings which stand for a missing date rather than a malformed one
+///
+/// Blank strings are always missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingTokens {
+    tokens: HashSet<String>,
+}
+
+impl MissingTokens {
+    pub fn new<I, S>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tokens = tokens
+            .into_iter()
+            .map(|t| normalise(t.as_ref()))
+            .filter(|t| !t.is_empty())
+            .collect();
+        MissingTokens { tokens }
+    }
+
+    /// Shared instance holding `DEFAULT_MISSING_TOKENS`
+    pub fn defaults() -> &'static MissingTokens {
+        DEFAULT.get_or_init(|| MissingTokens::new(DEFAULT_MISSING_TOKENS))
+    }
+
+    pub fn is_missing(&self, date: &str) -> bool {
+        let date = normalise(date);
+        date.is_empty() || self.tokens.contains(&date)
+    }
+}
+
+/// Form in which tokens and dates are compared
+fn normalise(value: &str) -> String {
+    value.trim().to_lowercase()
+}
@@ -75,6 +75,48 @@ fn get_month_lookup() -> &'static HashMap<&'static str, u8> {
     })
 }
 
+/// Month names grouped by language. Names shared by several languages
+/// (e.g. "april") are attributed to the first language listed.
+static MONTH_LANGUAGES: &[(&str, &[&str])] = &[
+    ("english", &[
+        "january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november", "december",
+        "jan", "feb", "mar", "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov", "dec",
+    ]),
+    ("french", &[
+        "janvier", "janv", "février", "fevrier", "févr", "fevr", "mars", "avril", "mai", "juin", "juillet", "juil",
+        "août", "aout", "septembre", "octobre", "novembre", "décembre", "decembre", "déc",
+    ]),
+    ("german", &[
+        "januar", "jänner", "jän", "februar", "märz", "marz", "juni", "juli", "oktober", "okt", "dezember", "dez",
+    ]),
+    ("spanish", &[
+        "enero", "ener", "ene", "febrero", "marzo", "abril", "abr", "mayo", "junio", "julio", "agosto",
+        "septiembre", "octubre", "noviembre", "diciembre", "dic",
+    ]),
+    ("portuguese", &[
+        "janeiro", "fevereiro", "fev", "março", "marco", "maio", "junho", "julho", "setembro", "set",
+        "outubro", "out", "novembro", "dezembro",
+    ]),
+    ("russian", &[
+        "январь", "января", "янв", "февраль", "февраля", "фев", "март", "мар", "апрель", "апреля", "апр",
+        "май", "мая", "июнь", "июня", "июн", "июль", "июля", "июл", "август", "авг",
+        "сентябрь", "сентября", "сент", "октябрь", "октября", "окт", "ноябрь", "ноября", "ноя",
+        "декабрь", "декабря", "дек",
+    ]),
+    ("indonesian", &[
+        "januari", "februari", "maret", "mei", "agustus", "desember",
+    ]),
+];
+
+/// Language a month name belongs to, if it is a known month name
+pub fn month_language(text: &str) -> Option<&'static str> {
+    let lower = text.to_lowercase();
+    MONTH_LANGUAGES
+        .iter()
+        .find(|(_, names)| names.contains(&lower.as_str()))
+        .map(|(language, _)| *language)
+}
+
 /// Optimized replace_all function using Cow to avoid unnecessary allocations
 pub fn replace_all_optimized<'a>(input: &'a str, patterns: &[(&str, &str)]) -> Cow<'a, str> {
     let mut result = Cow::Borrowed(input);
@@ -175,7 +217,7 @@ pub fn fast_path_parse_date(date: &str, format: &str) -> Option<(u8, u8, u16)> {
                     date[5..7].parse::<u8>(),
                     date[8..10].parse::<u8>(),
                 ) {
-                    if month >= 1 && month <= 12 && day >= 1 && day <= 31 {
+                    if (1..=12).contains(&month) && (1..=31).contains(&day) {
                         // Return even if day might be invalid for this month (e.g., Feb 30)
                         // The main validation logic will handle month-specific day limits
                         return Some((day, month, year));
@@ -193,24 +235,24 @@ pub fn fast_path_parse_date(date: &str, format: &str) -> Option<(u8, u8, u16)> {
                     match format {
                         "mdy" => {
                             // MM/DD/YYYY format
-                            if first >= 1 && first <= 12 && second >= 1 && second <= 31 {
+                            if (1..=12).contains(&first) && (1..=31).contains(&second) {
                                 return Some((second, first, year));
                             }
                         }
                         "dmy" => {
                             // DD/MM/YYYY format  
-                            if second >= 1 && second <= 12 && first >= 1 && first <= 31 {
+                            if (1..=12).contains(&second) && (1..=31).contains(&first) {
                                 return Some((first, second, year));
                             }
                         }
                         _ => {
                             // For unspecified format, only handle unambiguous cases
                             // If first > 12, must be DD/MM/YYYY
-                            if first > 12 && second >= 1 && second <= 12 {
+                            if first > 12 && (1..=12).contains(&second) {
                                 return Some((first, second, year));
                             }
                             // If second > 12, must be MM/DD/YYYY
-                            else if second > 12 && first >= 1 && first <= 12 {
+                            else if second > 12 && (1..=12).contains(&first) {
                                 return Some((second, first, year));
                             }
                             // Otherwise ambiguous - let main logic handle it
@@ -219,37 +261,35 @@ pub fn fast_path_parse_date(date: &str, format: &str) -> Option<(u8, u8, u16)> {
                 }
             }
         }
-        8 => {
+        8 if date.chars().nth(2) == Some('/') && date.chars().nth(5) == Some('/') => {
             // MM/DD/YY or DD/MM/YY format - only handle if format is specified
-            if date.chars().nth(2) == Some('/') && date.chars().nth(5) == Some('/') {
-                if let (Ok(first), Ok(second), Ok(year_short)) = (
-                    date[0..2].parse::<u8>(),
-                    date[3..5].parse::<u8>(),
-                    date[6..8].parse::<u8>(),
-                ) {
-                    let year = if year_short <= 30 { 2000 + year_short as u16 } else { 1900 + year_short as u16 };
-                    
-                    match format {
-                        "mdy" => {
-                            // MM/DD/YY format
-                            if first >= 1 && first <= 12 && second >= 1 && second <= 31 {
-                                return Some((second, first, year));
-                            }
+            if let (Ok(first), Ok(second), Ok(year_short)) = (
+                date[0..2].parse::<u8>(),
+                date[3..5].parse::<u8>(),
+                date[6..8].parse::<u8>(),
+            ) {
+                let year = if year_short <= 30 { 2000 + year_short as u16 } else { 1900 + year_short as u16 };
+                
+                match format {
+                    "mdy" => {
+                        // MM/DD/YY format
+                        if (1..=12).contains(&first) && (1..=31).contains(&second) {
+                            return Some((second, first, year));
                         }
-                        "dmy" => {
-                            // DD/MM/YY format
-                            if second >= 1 && second <= 12 && first >= 1 && first <= 31 {
-                                return Some((first, second, year));
-                            }
+                    }
+                    "dmy" => {
+                        // DD/MM/YY format
+                        if (1..=12).contains(&second) && (1..=31).contains(&first) {
+                            return Some((first, second, year));
                         }
-                        _ => {
-                            // For unspecified format, only handle unambiguous cases
-                            if first > 12 && second >= 1 && second <= 12 {
-                                return Some((first, second, year));
-                            }
-                            else if second > 12 && first >= 1 && first <= 12 {
-                                return Some((second, first, year));
-                            }
+                    }
+                    _ => {
+                        // For unspecified format, only handle unambiguous cases
+                        if first > 12 && (1..=12).contains(&second) {
+                            return Some((first, second, year));
+                        }
+                        else if second > 12 && (1..=12).contains(&first) {
+                            return Some((second, first, year));
                         }
                     }
                 }
@@ -260,12 +300,29 @@ pub fn fast_path_parse_date(date: &str, format: &str) -> Option<(u8, u8, u16)> {
     None
 }
 
+/// French articles and the ordinal "1er", as in "le 1er mars 2020"
+static FRENCH_REPLACEMENTS: &[(&str, &str)] = &[("le ", " "), ("Le ", " "), ("1er", "01")];
+
+/// Russian genitive month names and their nominative forms
+static RUSSIAN_GENITIVE_REPLACEMENTS: &[(&str, &str)] = &[
+    ("марта", "март"),
+    ("Марта", "Март"),
+    ("августа", "август"),
+    ("Августа", "Август"),
+];
+
+/// Whether any of `replacements` applies to `date`
+#[inline]
+fn needs_replacement(date: &str, replacements: &[(&str, &str)]) -> bool {
+    replacements.iter().any(|(from, _)| date.contains(from))
+}
+
 /// Combined string cleaning function to reduce multiple passes
 pub fn clean_date_string_combined(date: &str) -> Cow<'_, str> {
     // Check if any cleaning is needed first
     let needs_ordinal = date.contains("st") || date.contains("nd") || date.contains("rd") || date.contains("th") || date.contains('º') || date.contains('ª');
-    let needs_french = date.contains("le ") || date.contains("Le ") || date.contains("1er");
-    let needs_russian = date.contains("марта") || date.contains("Марта") || date.contains("августа") || date.contains("Августа");
+    let needs_french = needs_replacement(date, FRENCH_REPLACEMENTS);
+    let needs_russian = needs_replacement(date, RUSSIAN_GENITIVE_REPLACEMENTS);
     
     if !needs_ordinal && !needs_french && !needs_russian {
         let trimmed = date.trim();
@@ -285,17 +342,12 @@ pub fn clean_date_string_combined(date: &str) -> Cow<'_, str> {
     
     // Apply French replacements
     if needs_french {
-        result = replace_all_optimized(&result, &[("le ", " "), ("Le ", " "), ("1er", "01")]).into_owned();
+        result = replace_all_optimized(&result, FRENCH_REPLACEMENTS).into_owned();
     }
     
     // Apply Russian replacements
     if needs_russian {
-        result = replace_all_optimized(&result, &[
-            ("марта", "март"),
-            ("Марта", "Март"),
-            ("августа", "август"),
-            ("Августа", "Август"),
-        ]).into_owned();
+        result = replace_all_optimized(&result, RUSSIAN_GENITIVE_REPLACEMENTS).into_owned();
     }
     
     // Trim and return
@@ -303,6 +355,28 @@ pub fn clean_date_string_combined(date: &str) -> Cow<'_, str> {
     Cow::Owned(result)
 }
 
+/// Names of the `clean_date_string_combined` steps which change `date`, in
+/// the order they are applied
+pub fn cleaning_steps(date: &str) -> Vec<&'static str> {
+    let mut steps = Vec::new();
+    let ordinal = rm_ordinal_suffixes_optimized(date);
+    if ordinal != date {
+        steps.push("ordinal_suffix");
+    }
+    let french = replace_all_optimized(&ordinal, FRENCH_REPLACEMENTS);
+    if french != ordinal {
+        steps.push("french_article");
+    }
+    let russian = replace_all_optimized(&french, RUSSIAN_GENITIVE_REPLACEMENTS);
+    if russian != french {
+        steps.push("russian_genitive");
+    }
+    if russian.trim() != russian {
+        steps.push("trim");
+    }
+    steps
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -347,6 +421,32 @@ mod tests {
         assert_eq!(rm_ordinal_suffixes_optimized("1ª jan"), "1 jan");
     }
     
+    #[test]
+    fn test_cleaning_steps() {
+        assert_eq!(cleaning_steps("2020-01-01"), Vec::<&str>::new());
+        assert_eq!(cleaning_steps("July 4th, 1776"), vec!["ordinal_suffix"]);
+        assert_eq!(cleaning_steps("le 1er mars 2013 "), vec!["french_article", "trim"]);
+        assert_eq!(cleaning_steps("8 марта 2020"), vec!["russian_genitive"]);
+    }
+
+    #[test]
+    fn test_month_language() {
+        assert_eq!(month_language("January"), Some("english"));
+        assert_eq!(month_language("janvier"), Some("french"));
+        assert_eq!(month_language("Dezember"), Some("german"));
+        assert_eq!(month_language("enero"), Some("spanish"));
+        assert_eq!(month_language("março"), Some("portuguese"));
+        assert_eq!(month_language("марта"), None);
+        assert_eq!(month_language("мая"), Some("russian"));
+        assert_eq!(month_language("agustus"), Some("indonesian"));
+        assert_eq!(month_language("2020"), None);
+
+        // Every recognised month name is attributed to a language
+        for name in get_month_lookup().keys() {
+            assert!(month_language(name).is_some(), "No language for {}", name);
+        }
+    }
+
     #[test]
     fn test_fast_path_parse_date() {
         // Test ISO format (always unambiguous)
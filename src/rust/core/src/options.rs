@@ -0,0 +1,262 @@
+use crate::error::{Component, DateFixError};
+use crate::missing::MissingTokens;
+use crate::pipeline::days_in_month;
+use std::sync::Arc;
+
+/// What to do with a date which is missing its day or month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Imputation {
+    /// Start of the period: the 1st, or January
+    First,
+    /// Middle of the period: the 15th, or June. A date with only a year is
+    /// imputed as 30 June when both its day and month use `Middle`
+    Middle,
+    /// End of the period: the last day of the month, or December
+    Last,
+    /// Use this day of the month, or month of the year
+    Value(i32),
+    /// Resolve the whole date to NA
+    Na,
+    /// Fail with a missing day or missing month error
+    Error,
+}
+
+impl Imputation {
+    /// Policy named `"first"`, `"middle"` or `"last"`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "first" => Some(Imputation::First),
+            "middle" => Some(Imputation::Middle),
+            "last" => Some(Imputation::Last),
+            _ => None,
+        }
+    }
+
+    /// Whether a date missing the component still resolves to a date
+    pub fn imputes(self) -> bool {
+        !matches!(self, Imputation::Na | Imputation::Error)
+    }
+
+    /// Month imputed for a date without one, `None` for NA and error policies
+    pub fn month(self) -> Option<i32> {
+        match self {
+            Imputation::First => Some(1),
+            Imputation::Middle => Some(6),
+            Imputation::Last => Some(12),
+            Imputation::Value(month) => Some(month),
+            Imputation::Na | Imputation::Error => None,
+        }
+    }
+
+    /// Day imputed for a date in `month` of `year` without one, `None` for
+    /// NA and error policies
+    pub fn day(self, year: i32, month: i32) -> Option<i32> {
+        match self {
+            Imputation::First => Some(1),
+            Imputation::Middle => Some(15),
+            Imputation::Last => Some(days_in_month(year, month)),
+            Imputation::Value(day) => Some(day),
+            Imputation::Na | Imputation::Error => None,
+        }
+    }
+}
+
+/// Day/month order used for ambiguous numeric dates such as "01/02/2023"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateFormat {
+    /// Day before month
+    #[default]
+    Dmy,
+    /// Month before day, as in the US
+    Mdy,
+    /// Inferred from the dates only valid in one order. A lone date is read
+    /// day first unless it is only valid month first
+    Auto,
+}
+
+impl DateFormat {
+    /// Order named `"dmy"`, `"mdy"` or `"auto"`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dmy" => Some(DateFormat::Dmy),
+            "mdy" => Some(DateFormat::Mdy),
+            "auto" => Some(DateFormat::Auto),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DateFormat::Dmy => "dmy",
+            DateFormat::Mdy => "mdy",
+            DateFormat::Auto => "auto",
+        }
+    }
+}
+
+/// Hemisphere whose seasons season names such as "summer" refer to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Hemisphere {
+    /// Summer is June to August
+    #[default]
+    Northern,
+    /// Summer is December to February
+    Southern,
+}
+
+impl Hemisphere {
+    /// Hemisphere named `"north"` or `"south"`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "north" => Some(Hemisphere::Northern),
+            "south" => Some(Hemisphere::Southern),
+            _ => None,
+        }
+    }
+}
+
+/// Settings for parsing dates, built once per call
+///
+/// ```
+/// use datefixr_core::{DateFormat, Imputation, ParseOptions};
+///
+/// let options = ParseOptions::new()
+///     .day_impute(Imputation::Value(15))
+///     .month_impute(Imputation::Error)
+///     .format(DateFormat::Mdy);
+/// assert_eq!(options.format, DateFormat::Mdy);
+/// assert!(options.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Imputation for dates without a day, 1 by default
+    pub day_impute: Imputation,
+    /// Imputation for dates without a month, 7 (July) by default
+    pub month_impute: Imputation,
+    /// Day/month order, only used for ambiguous numeric dates
+    pub format: DateFormat,
+    /// Read plain numbers as Excel serials rather than days since 1970
+    pub excel: bool,
+    /// Recognise Roman numeral months
+    pub roman_numeral: bool,
+    /// Reject day clamping, imputation and two digit years
+    pub strict: bool,
+    /// Hemisphere used to read season names, northern by default
+    pub hemisphere: Hemisphere,
+    /// Read Extended Date/Time Format syntax such as "2004?", "201X" or
+    /// "2001-21", off by default
+    pub edtf: bool,
+    /// Strings standing for a missing date, `DEFAULT_MISSING_TOKENS` when
+    /// not set. Shared so that cloning the options stays cheap
+    pub missing_tokens: Option<Arc<MissingTokens>>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            day_impute: Imputation::Value(1),
+            month_impute: Imputation::Value(7),
+            format: DateFormat::Dmy,
+            excel: false,
+            roman_numeral: false,
+            strict: false,
+            hemisphere: Hemisphere::Northern,
+            edtf: false,
+            missing_tokens: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn day_impute(mut self, day_impute: Imputation) -> Self {
+        self.day_impute = day_impute;
+        self
+    }
+
+    pub fn month_impute(mut self, month_impute: Imputation) -> Self {
+        self.month_impute = month_impute;
+        self
+    }
+
+    pub fn format(mut self, format: DateFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn excel(mut self, excel: bool) -> Self {
+        self.excel = excel;
+        self
+    }
+
+    pub fn roman_numeral(mut self, roman_numeral: bool) -> Self {
+        self.roman_numeral = roman_numeral;
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn hemisphere(mut self, hemisphere: Hemisphere) -> Self {
+        self.hemisphere = hemisphere;
+        self
+    }
+
+    pub fn edtf(mut self, edtf: bool) -> Self {
+        self.edtf = edtf;
+        self
+    }
+
+    pub fn missing_tokens(mut self, missing_tokens: MissingTokens) -> Self {
+        self.missing_tokens = Some(Arc::new(missing_tokens));
+        self
+    }
+
+    /// Missing-value tokens in use, the shared defaults unless others were set
+    pub fn missing(&self) -> &MissingTokens {
+        self.missing_tokens
+            .as_deref()
+            .unwrap_or_else(|| MissingTokens::defaults())
+    }
+
+    /// Check that imputation values are a day of the month and a month
+    ///
+    /// The pipeline runs this first, so an out of range value is reported as
+    /// such rather than as a date whose day or month is out of range.
+    pub fn validate(&self) -> Result<(), DateFixError> {
+        let check = |impute: Imputation, component: Component, max: i32| match impute {
+            Imputation::Value(value) if !(1..=max).contains(&value) => {
+                Err(DateFixError::InvalidImputation { component, value })
+            }
+            _ => Ok(()),
+        };
+        check(self.day_impute, Component::Day, 31)?;
+        check(self.month_impute, Component::Month, 12)
+    }
+
+    /// Imputation actually applied to missing days
+    ///
+    /// Strict mode imputes nothing, so incomplete dates hit the same errors
+    /// as when no imputation value is given.
+    pub fn effective_day_impute(&self) -> Imputation {
+        if self.strict {
+            Imputation::Error
+        } else {
+            self.day_impute
+        }
+    }
+
+    /// Imputation actually applied to missing months, see `effective_day_impute`
+    pub fn effective_month_impute(&self) -> Imputation {
+        if self.strict {
+            Imputation::Error
+        } else {
+            self.month_impute
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::approx::Qualifier;
+use crate::pipeline::days_in_month;
+use crate::time::TimeOfDay;
+
+/// `num_days_from_ce()` of 1970-01-01, the origin of R's `Date` class
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Pipeline stage which resolved a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateSource {
+    /// Fixed-width ISO or slash separated date handled by `fast_path_parse_date`
+    FastPath,
+    /// Numeric components split by `separate_date_optimized`
+    Numeric,
+    /// Month given by name and converted to a number
+    TextMonth,
+    /// Month given as a Roman numeral
+    RomanNumeral,
+    /// Four digit year with imputed month and day
+    YearOnly,
+    /// Excel serial day count
+    ExcelSerial,
+    /// Days since the Unix epoch
+    UnixSerial,
+    /// ISO 8601 week date, e.g. "2020-W05-3"
+    IsoWeek,
+    /// ISO 8601 ordinal date, e.g. "2020-123"
+    Ordinal,
+    /// Quarter of a year with imputed month and day, e.g. "Q3 2020"
+    Quarter,
+    /// Season of a year with imputed month and day, e.g. "Summer 2019"
+    Season,
+    /// Decade with imputed year, month and day, e.g. "1990s"
+    Decade,
+    /// Century with imputed year, month and day, e.g. "19XX" in EDTF
+    Century,
+    /// Extended Date/Time Format date, e.g. "2004-06-XX" or "-0044-03-15"
+    Edtf,
+}
+
+impl DateSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DateSource::FastPath => "fast_path",
+            DateSource::Numeric => "numeric",
+            DateSource::TextMonth => "text_month",
+            DateSource::RomanNumeral => "roman_numeral",
+            DateSource::YearOnly => "year_only",
+            DateSource::ExcelSerial => "excel_serial",
+            DateSource::UnixSerial => "unix_serial",
+            DateSource::IsoWeek => "iso_week",
+            DateSource::Ordinal => "ordinal",
+            DateSource::Quarter => "quarter",
+            DateSource::Season => "season",
+            DateSource::Decade => "decade",
+            DateSource::Century => "century",
+            DateSource::Edtf => "edtf",
+        }
+    }
+}
+
+/// Most precise component given in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precision {
+    /// Century, e.g. "19XX"
+    Century,
+    /// Decade, e.g. "1990s" or "the 90s"
+    Decade,
+    /// Only the year, e.g. "2020"
+    Year,
+    /// Season, e.g. "Summer 2019" or "winter 2018/19"
+    Season,
+    /// Year and quarter, e.g. "Q3 2020"
+    Quarter,
+    /// Year and month, e.g. "March 2020" or "03/2020"
+    Month,
+    /// ISO 8601 year and week, e.g. "2020-W05"
+    Week,
+    /// A full date
+    Day,
+}
+
+impl Precision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Precision::Century => "century",
+            Precision::Decade => "decade",
+            Precision::Year => "year",
+            Precision::Season => "season",
+            Precision::Quarter => "quarter",
+            Precision::Month => "month",
+            Precision::Week => "week",
+            Precision::Day => "day",
+        }
+    }
+}
+
+/// A successfully tidied date along with how it was obtained
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedDate {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    /// Day was not given and the imputation value was used
+    pub day_imputed: bool,
+    /// Month was not given and the imputation value was used
+    pub month_imputed: bool,
+    /// Day exceeded the length of the month and was lowered by `check_output`
+    pub day_clamped: bool,
+    /// Component order used to read the date, e.g. `"dmy"`, `"ymd"` or `"my"`.
+    /// `None` for serial numbers
+    pub format: Option<&'static str>,
+    /// Language of the month name, if the month was given by name
+    pub language: Option<&'static str>,
+    pub source: DateSource,
+    /// Time of day following the date, if one was given
+    pub time: Option<TimeOfDay>,
+    /// Qualifier the date was given with, such as "circa" or "early"
+    pub qualifier: Option<Qualifier>,
+    /// Date was marked as uncertain, as with "2004?" in EDTF
+    pub uncertain: bool,
+}
+
+impl ParsedDate {
+    /// Fully specified date with no imputation or adjustment
+    pub fn new(year: i32, month: i32, day: i32, source: DateSource) -> Self {
+        ParsedDate {
+            year,
+            month,
+            day,
+            day_imputed: false,
+            month_imputed: false,
+            day_clamped: false,
+            format: None,
+            language: None,
+            source,
+            time: None,
+            qualifier: None,
+            uncertain: false,
+        }
+    }
+
+    /// Days since 1970-01-01, the representation used by R's `Date` class
+    pub fn days_since_epoch(&self) -> Option<f64> {
+        NaiveDate::from_ymd_opt(self.year, self.month as u32, self.day as u32)
+            .map(|d| (d.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE) as f64)
+    }
+
+    /// Seconds since 1970-01-01 00:00:00 UTC, the representation used by R's
+    /// `POSIXct` class
+    ///
+    /// A time without an offset is read as UTC, and a date without a time as
+    /// midnight.
+    pub fn seconds_since_epoch(&self) -> Option<f64> {
+        let days = self.days_since_epoch()?;
+        let time = self.time.map_or(0.0, |t| {
+            t.seconds() - t.offset.unwrap_or(0) as f64 * 60.0
+        });
+        Some(days * 86_400.0 + time)
+    }
+
+    /// Format as `YYYY-MM-DD`
+    pub fn to_iso_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Whether the date was given with a qualifier such as "circa" or "early"
+    pub fn approximate(&self) -> bool {
+        self.qualifier.is_some()
+    }
+
+    /// Precision of the input, from the components which were not imputed
+    pub fn precision(&self) -> Precision {
+        if self.month_imputed && self.source == DateSource::Century {
+            Precision::Century
+        } else if self.month_imputed && self.source == DateSource::Decade {
+            Precision::Decade
+        } else if self.month_imputed && self.source == DateSource::Season {
+            Precision::Season
+        } else if self.month_imputed && self.source == DateSource::Quarter {
+            Precision::Quarter
+        } else if self.month_imputed {
+            Precision::Year
+        } else if self.day_imputed && self.source == DateSource::IsoWeek {
+            Precision::Week
+        } else if self.day_imputed {
+            Precision::Month
+        } else {
+            Precision::Day
+        }
+    }
+
+    /// First and last day the input could refer to, given its precision
+    ///
+    /// Centuries and decades span their years, a year-only date the whole
+    /// year and a date without a day the whole month, accounting for leap
+    /// years. Quarters and seasons span their three months, with winter
+    /// running from December into the next year in the northern hemisphere,
+    /// and weeks run from Monday to Sunday. Full dates span one day. An early,
+    /// mid or late qualifier narrows the interval to a third of the period.
+    pub fn interval(&self) -> (ParsedDate, ParsedDate) {
+        let (lower, upper) = self.period();
+        match self.qualifier.and_then(Qualifier::third) {
+            Some(third) => self.narrow(lower, upper, third),
+            None => (lower, upper),
+        }
+    }
+
+    /// First and last day of the whole period given
+    fn period(&self) -> (ParsedDate, ParsedDate) {
+        let with_day = |month: i32, day: i32| ParsedDate {
+            month,
+            day,
+            ..self.clone()
+        };
+        let with_year = |year: i32, month: i32, day: i32| ParsedDate {
+            year,
+            month,
+            day,
+            ..self.clone()
+        };
+        match self.precision() {
+            Precision::Century => {
+                let first = self.year.div_euclid(100) * 100;
+                (with_year(first, 1, 1), with_year(first + 99, 12, 31))
+            }
+            Precision::Decade => {
+                let first = self.year.div_euclid(10) * 10;
+                (with_year(first, 1, 1), with_year(first + 9, 12, 31))
+            }
+            Precision::Year => (with_day(1, 1), with_day(12, 31)),
+            Precision::Quarter => {
+                let first = (self.month - 1) / 3 * 3 + 1;
+                let last = first + 2;
+                (
+                    with_day(first, 1),
+                    with_day(last, days_in_month(self.year, last)),
+                )
+            }
+            Precision::Month => (
+                with_day(self.month, 1),
+                with_day(self.month, days_in_month(self.year, self.month)),
+            ),
+            Precision::Season => {
+                // Seasons in both hemispheres start in March, June,
+                // September or December
+                let (year, first) = match self.month {
+                    1 | 2 => (self.year - 1, 12),
+                    month => (self.year, month / 3 * 3),
+                };
+                let (last_year, last) = if first == 12 {
+                    (year + 1, 2)
+                } else {
+                    (year, first + 2)
+                };
+                let lower = ParsedDate {
+                    year,
+                    month: first,
+                    day: 1,
+                    ..self.clone()
+                };
+                let upper = ParsedDate {
+                    year: last_year,
+                    month: last,
+                    day: days_in_month(last_year, last),
+                    ..self.clone()
+                };
+                (lower, upper)
+            }
+            Precision::Week => {
+                let with_date = |date: NaiveDate| ParsedDate {
+                    year: date.year(),
+                    month: date.month() as i32,
+                    day: date.day() as i32,
+                    ..self.clone()
+                };
+                let date = NaiveDate::from_ymd_opt(self.year, self.month as u32, self.day as u32)
+                    .expect("week dates are built from valid dates");
+                let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (with_date(monday), with_date(monday + Duration::days(6)))
+            }
+            Precision::Day => (self.clone(), self.clone()),
+        }
+    }
+
+    /// The early (0), mid (1) or late (2) third of the period from `lower`
+    /// to `upper`
+    ///
+    /// Centuries split into years 0-32, 33-65 and 66-99, decades into years
+    /// 0-3, 4-6 and 7-9, years into four months each, quarters and seasons
+    /// into one month each, and months into days 1-10, 11-20 and 21 to the
+    /// end. Weeks and full dates are not narrowed.
+    fn narrow(
+        &self,
+        lower: ParsedDate,
+        upper: ParsedDate,
+        third: usize,
+    ) -> (ParsedDate, ParsedDate) {
+        let with_date = |year: i32, month: i32, day: i32| ParsedDate {
+            year,
+            month,
+            day,
+            ..self.clone()
+        };
+        let month_of = |year: i32, month: i32| {
+            (
+                with_date(year, month, 1),
+                with_date(year, month, days_in_month(year, month)),
+            )
+        };
+        match self.precision() {
+            Precision::Century => {
+                let (first, last) = [(0, 32), (33, 65), (66, 99)][third];
+                (
+                    with_date(lower.year + first, 1, 1),
+                    with_date(lower.year + last, 12, 31),
+                )
+            }
+            Precision::Decade => {
+                let (first, last) = [(0, 3), (4, 6), (7, 9)][third];
+                (
+                    with_date(lower.year + first, 1, 1),
+                    with_date(lower.year + last, 12, 31),
+                )
+            }
+            Precision::Year => {
+                let first = 1 + 4 * third as i32;
+                (
+                    with_date(lower.year, first, 1),
+                    with_date(lower.year, first + 3, days_in_month(lower.year, first + 3)),
+                )
+            }
+            Precision::Season | Precision::Quarter => match lower.month + third as i32 {
+                month if month > 12 => month_of(lower.year + 1, month - 12),
+                month => month_of(lower.year, month),
+            },
+            Precision::Month => {
+                let (first, last) = [(1, 10), (11, 20), (21, upper.day)][third];
+                (
+                    with_date(lower.year, lower.month, first),
+                    with_date(lower.year, lower.month, last),
+                )
+            }
+            Precision::Week | Precision::Day => (lower, upper),
+        }
+    }
+
+    /// Format at the precision of the input as an ISO 8601 reduced precision
+    /// date: `YYYY` when the month was imputed and `YYYY-MM` when the day was,
+    /// as used for SDTM `--DTC` variables
+    pub fn to_partial_iso_string(&self) -> String {
+        match self.precision() {
+            // ISO 8601 has no quarters, only the year is known for certain
+            Precision::Year | Precision::Quarter => format!("{:04}", self.year),
+            // Centuries, decades and seasons running into the next year are
+            // intervals of years
+            Precision::Century | Precision::Decade | Precision::Season => {
+                let (lower, upper) = self.period();
+                if lower.year == upper.year {
+                    format!("{:04}", lower.year)
+                } else {
+                    format!("{:04}/{:04}", lower.year, upper.year)
+                }
+            }
+            Precision::Month => format!("{:04}-{:02}", self.year, self.month),
+            Precision::Week => {
+                let week = NaiveDate::from_ymd_opt(self.year, self.month as u32, self.day as u32)
+                    .map(|d| d.iso_week());
+                match week {
+                    Some(week) => format!("{:04}-W{:02}", week.year(), week.week()),
+                    None => self.to_iso_string(),
+                }
+            }
+            Precision::Day => self.to_iso_string(),
+        }
+    }
+
+    /// ADaM `--DTF` imputation flag: `"Y"` when the year was imputed within a
+    /// decade or century, `"M"` when the month was imputed, `"D"` when only the day was
+    pub fn imputation_flag(&self) -> Option<&'static str> {
+        match self.precision() {
+            Precision::Century | Precision::Decade => Some("Y"),
+            Precision::Year | Precision::Season | Precision::Quarter => Some("M"),
+            Precision::Month | Precision::Week => Some("D"),
+            Precision::Day => None,
+        }
+    }
+
+    /// Format as an Extended Date/Time Format (EDTF) level 0 or 1 string
+    ///
+    /// Centuries and decades keep their unspecified digits ("19XX", "199X"),
+    /// and years and months their reduced precision forms. Quarters, seasons,
+    /// weeks and periods narrowed by an early, mid or late qualifier become
+    /// intervals such as "2020-07/2020-09". Approximate dates are marked with
+    /// "~", uncertain ones with "?" and dates which are both with "%". A time
+    /// of day is kept on full dates, which then carry no marker.
+    pub fn to_edtf_string(&self) -> String {
+        let marker = match (self.approximate(), self.uncertain) {
+            (true, true) => "%",
+            (true, false) => "~",
+            (false, true) => "?",
+            (false, false) => "",
+        };
+        let month = |d: &ParsedDate| format!("{}-{:02}", edtf_year(d.year), d.month);
+        let day = |d: &ParsedDate| format!("{}-{:02}", month(d), d.day);
+        let narrowed = self.qualifier.and_then(Qualifier::third).is_some();
+        let (lower, upper) = self.interval();
+
+        let (start, end) = match self.precision() {
+            Precision::Century if !narrowed => (format!("{:02}XX", self.year / 100), None),
+            Precision::Decade if !narrowed => (format!("{:03}X", self.year / 10), None),
+            Precision::Century | Precision::Decade => {
+                (edtf_year(lower.year), Some(edtf_year(upper.year)))
+            }
+            Precision::Year if !narrowed => (edtf_year(self.year), None),
+            Precision::Year | Precision::Season | Precision::Quarter => {
+                if (lower.year, lower.month) == (upper.year, upper.month) {
+                    (month(&lower), None)
+                } else {
+                    (month(&lower), Some(month(&upper)))
+                }
+            }
+            Precision::Month if !narrowed => (month(self), None),
+            Precision::Month | Precision::Week => (day(&lower), Some(day(&upper))),
+            Precision::Day => match self.time {
+                Some(time) => return format!("{}T{}", day(self), time),
+                None => (day(self), None),
+            },
+        };
+        match end {
+            Some(end) => format!("{}{}/{}{}", start, marker, end, marker),
+            None => format!("{}{}", start, marker),
+        }
+    }
+}
+
+/// EDTF year: four digits, a minus sign before years BC, and a leading "Y"
+/// for years of more than four digits
+fn edtf_year(year: i32) -> String {
+    match year {
+        0..=9999 => format!("{:04}", year),
+        -9999..=-1 => format!("-{:04}", -year),
+        _ => format!("Y{}", year),
+    }
+}
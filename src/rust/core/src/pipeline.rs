@@ -0,0 +1,2424 @@
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::approx::*;
+use crate::edtf::*;
+use crate::error::*;
+use crate::inference::*;
+use crate::options::*;
+use crate::optimizations::*;
+use crate::parsed::*;
+use crate::time::*;
+use crate::trace::*;
+
+/// Month names in different languages (mirroring R months data)
+static MONTHS: OnceLock<HashMap<usize, Vec<&'static str>>> = OnceLock::new();
+static ROMAN_NUMERALS: OnceLock<Vec<&'static str>> = OnceLock::new();
+static DAYS_IN_MONTH: [u8; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+static CURRENT_YEAR: OnceLock<i32> = OnceLock::new();
+
+/// Day, month and year components of a date
+type DateParts = (Option<i32>, Option<i32>, Option<i32>);
+
+/// Get cached current year to avoid repeated system calls
+#[inline]
+fn get_current_year() -> i32 {
+    *CURRENT_YEAR.get_or_init(|| chrono::Utc::now().year())
+}
+
+
+fn get_months() -> &'static HashMap<usize, Vec<&'static str>> {
+    MONTHS.get_or_init(|| {
+        let mut months = HashMap::new();
+        months.insert(
+            1,
+            vec![
+                "january",
+                "janvier",
+                "janeiro",
+                "janv",
+                "januar",
+                "jänner",
+                "jän",
+                "enero",
+                "ener",
+                "ene",
+                "jan",
+                "январь",
+                "января",
+                "янв",
+                "januari",
+            ],
+        );
+        months.insert(
+            2,
+            vec![
+                "february",
+                "février",
+                "fevrier",
+                "fevereiro",
+                "févr",
+                "fevr",
+                "fev",
+                "februar",
+                "febrero",
+                "feb",
+                "февраль",
+                "февраля",
+                "фев",
+                "februari",
+            ],
+        );
+        months.insert(
+            3,
+            vec![
+                "march", "mars", "märz", "marzo", "março", "marco", "marz", "mar", "март", "мар",
+                "maret",
+            ],
+        );
+        months.insert(
+            4,
+            vec![
+                "april",
+                "avril",
+                "abril",
+                "abr",
+                "apr",
+                "апрель",
+                "апреля",
+                "апр",
+            ],
+        );
+        months.insert(5, vec!["mayo", "may", "maio", "mai", "май", "мая", "mei"]);
+        months.insert(
+            6,
+            vec![
+                "june", "juin", "junio", "junho", "juni", "jun", "июнь", "июня", "июн",
+            ],
+        );
+        months.insert(
+            7,
+            vec![
+                "july", "juillet", "juil", "julio", "julho", "juli", "jul", "июль", "июля", "июл",
+            ],
+        );
+        months.insert(
+            8,
+            vec![
+                "august",
+                "aug",
+                "août",
+                "aout",
+                "agosto",
+                "август",
+                "авг",
+                "agustus",
+            ],
+        );
+        months.insert(
+            9,
+            vec![
+                "september",
+                "septembre",
+                "septiembre",
+                "setembro",
+                "set",
+                "sept",
+                "sep",
+                "сентябрь",
+                "сентября",
+                "сент",
+            ],
+        );
+        months.insert(
+            10,
+            vec![
+                "october",
+                "octobre",
+                "oktober",
+                "okt",
+                "octubre",
+                "outubro",
+                "oct",
+                "out",
+                "октябрь",
+                "октября",
+                "окт",
+            ],
+        );
+        months.insert(
+            11,
+            vec![
+                "november",
+                "novembre",
+                "noviembre",
+                "novembro",
+                "nov",
+                "ноябрь",
+                "ноября",
+                "ноя",
+            ],
+        );
+        months.insert(
+            12,
+            vec![
+                "december",
+                "décembre",
+                "decembre",
+                "déc",
+                "dezember",
+                "dezembro",
+                "dez",
+                "diciembre",
+                "dic",
+                "dec",
+                "декабрь",
+                "декабря",
+                "дек",
+                "desember",
+            ],
+        );
+        months
+    })
+}
+
+fn get_roman_numerals() -> &'static Vec<&'static str> {
+    ROMAN_NUMERALS.get_or_init(|| {
+        vec![
+            "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii",
+        ]
+    })
+}
+
+/// Add year prefix to 2-digit years
+#[inline]
+fn year_prefix(year: &str) -> String {
+    if year.len() == 2 {
+        let current_year = get_current_year();
+        let current_two_digit = current_year % 100;
+        let year_num: i32 = year.parse().unwrap_or(0);
+
+        if year_num <= current_two_digit {
+            format!("20{}", year)
+        } else {
+            format!("19{}", year)
+        }
+    } else {
+        year.to_string()
+    }
+}
+
+/// Append year to date vector if needed
+///
+/// In strict mode a two digit year is an error rather than given a century.
+fn append_year(mut date_vec: Vec<String>, strict: bool) -> Result<Vec<String>, DateFixError> {
+    let all_short = date_vec.iter().all(|s| s.len() <= 2 && !s.is_empty());
+
+    if all_short {
+        let year_index = match date_vec.len() {
+            3 if date_vec[2].len() == 2 => Some(2),
+            2 if date_vec[1].len() == 2 => Some(1),
+            _ => None,
+        };
+        if let Some(i) = year_index {
+            if strict {
+                return Err(DateFixError::TwoDigitYear {
+                    year: date_vec[i].clone(),
+                    input: String::new(),
+                });
+            }
+            date_vec[i] = year_prefix(&date_vec[i]);
+        }
+    }
+    Ok(date_vec)
+}
+
+/// Convert Roman numerals to Arabic numbers
+fn roman_conversion(mut date_vec: Vec<String>) -> Vec<String> {
+    if date_vec.len() >= 2 {
+        let roman_numerals = get_roman_numerals();
+        let month_str = date_vec[1].to_lowercase();
+
+        if let Some(pos) = roman_numerals.iter().position(|r| *r == month_str) {
+            let month_num = pos + 1;
+            date_vec[1] = if month_num < 10 {
+                format!("0{}", month_num)
+            } else {
+                month_num.to_string()
+            };
+        }
+    }
+    date_vec
+}
+
+/// Check if string contains only digits
+#[inline]
+fn is_numeric(s: &str) -> bool {
+    !s.chars().any(|c| !c.is_ascii_digit())
+}
+
+/// Helper function to clean numeric strings by removing trailing punctuation
+#[inline]
+fn clean_numeric_string(s: &str) -> &str {
+    s.trim_end_matches(|c: char| !c.is_ascii_digit())
+}
+
+/// Check if first element of date vector is a month name
+fn first_is_month(date_vec: &[String]) -> bool {
+    if date_vec.is_empty() {
+        return false;
+    }
+
+    let first_lower = date_vec[0].to_lowercase();
+    let months = get_months();
+
+    for month_names in months.values() {
+        if month_names.contains(&first_lower.as_str()) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Number of days in a month, accounting for leap years
+///
+/// Months outside 1-12 give 31 so that `check_output` reports the month.
+pub(crate) fn days_in_month(year: i32, month: i32) -> i32 {
+    if !(1..=12).contains(&month) {
+        return 31;
+    }
+    if month == 2 && ((year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)) {
+        return 29;
+    }
+    DAYS_IN_MONTH[month as usize - 1] as i32
+}
+
+/// Validate and adjust date components
+///
+/// Days past the end of the month are lowered to the last day, or rejected
+/// in strict mode.
+pub(crate) fn check_output(
+    day: Option<i32>,
+    month: Option<i32>,
+    year: Option<i32>,
+    strict: bool,
+) -> Result<DateParts, DateFixError> {
+    if let Some(m) = month {
+        if !(1..=12).contains(&m) {
+            return Err(DateFixError::MonthOutOfRange {
+                month: m,
+                input: String::new(),
+            });
+        }
+    }
+
+    let mut adjusted_day = day;
+
+    if let (Some(d), Some(m), Some(y)) = (day, month, year) {
+        if !(1..=31).contains(&d) {
+            // Day validation failed
+            return Err(DateFixError::DayOutOfRange {
+                day: d,
+                input: String::new(),
+            });
+        }
+
+        let max_days = days_in_month(y, m);
+
+        if d > max_days {
+            if strict {
+                return Err(DateFixError::DayOutOfRange {
+                    day: d,
+                    input: String::new(),
+                });
+            }
+            adjusted_day = Some(max_days);
+        }
+    }
+
+    Ok((adjusted_day, month, year))
+}
+
+/// Combine partial date components into a (year, month, day) triple
+fn combine_partial_date(
+    day: Option<i32>,
+    month: Option<i32>,
+    year: Option<i32>,
+) -> Result<(i32, i32, i32), DateFixError> {
+    match (day, month, year) {
+        (Some(d), Some(m), Some(y)) => Ok((y, m, d)),
+        _ => Err(DateFixError::NaImputed {
+            input: String::new(),
+        }),
+    }
+}
+
+/// Handle 4-digit year only dates (e.g., "2020")
+fn handle_year_only_date(
+    cleaned_date: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    strict: bool,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    if cleaned_date.len() == 4 && is_numeric(cleaned_date) {
+        let year = cleaned_date.parse::<i32>().unwrap();
+        return impute_year_only(year, cleaned_date, day_impute, month_impute, strict).map(Some);
+    }
+    // Not a year-only date, continue with other parsing
+    Ok(None)
+}
+
+/// Impute the month and day of a date of which only `year` was given
+///
+/// A numeric day imputation past the end of the imputed month is lowered to
+/// its last day, or rejected in strict mode.
+pub(crate) fn impute_year_only(
+    year: i32,
+    input: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    strict: bool,
+) -> Result<ParsedDate, DateFixError> {
+    let input = input.to_string();
+    match (day_impute, month_impute) {
+        // Either month.impute or day.impute is NA, so the date is NA
+        (Imputation::Na, _) | (_, Imputation::Na) => Err(DateFixError::NaImputed { input }),
+        (_, Imputation::Error) => Err(DateFixError::MissingMonth { input }),
+        (Imputation::Error, _) => Err(DateFixError::MissingDay { input }),
+        (Imputation::Middle, Imputation::Middle) => Ok(year_only(year, 6, 30)),
+        (day_impute, month_impute) => {
+            let m = month_impute.month().unwrap();
+            let d = day_impute.day(year, m).unwrap();
+            let (adjusted, _, _) = check_output(Some(d), Some(m), Some(year), strict)?;
+            let adjusted = adjusted.unwrap();
+            let mut parsed = year_only(year, m, adjusted);
+            parsed.day_clamped = adjusted != d;
+            Ok(parsed)
+        }
+    }
+}
+
+/// Impute a year from `first` to `last`, then its month and day
+///
+/// `First`, `Middle` and `Last` month imputation pick the first, middle or
+/// last year, and a numeric month imputation value the first.
+pub(crate) fn impute_year_span(
+    first: i32,
+    last: i32,
+    source: DateSource,
+    input: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    strict: bool,
+) -> Result<ParsedDate, DateFixError> {
+    let year = match month_impute {
+        Imputation::Middle => (first + last) / 2,
+        Imputation::Last => last,
+        _ => first,
+    };
+    let parsed = impute_year_only(year, input, day_impute, month_impute, strict)?;
+    Ok(ParsedDate { source, ..parsed })
+}
+
+/// A date of which only the year was given
+fn year_only(year: i32, month: i32, day: i32) -> ParsedDate {
+    let mut parsed = ParsedDate::new(year, month, day, DateSource::YearOnly);
+    parsed.day_imputed = true;
+    parsed.month_imputed = true;
+    parsed.format = Some("y");
+    parsed
+}
+
+/// Quarters: "Q3 2020", "2020Q3", "2020-Q3", "3Q20", "T3 2020" (French
+/// trimestre), "3. Quartal 2020" and "3rd quarter 2020" once cleaned
+fn quarter_regexes() -> &'static [Regex] {
+    static QUARTER_REGEXES: OnceLock<Vec<Regex>> = OnceLock::new();
+    QUARTER_REGEXES.get_or_init(|| {
+        [
+            r"^[qt](?P<quarter>[1-4])[\s/-]*(?P<year>\d{4}|\d{2})$",
+            r"^(?P<year>\d{4})[\s/-]*[qt](?P<quarter>[1-4])$",
+            r"^(?P<quarter>[1-4])q[\s/-]*(?P<year>\d{4}|\d{2})$",
+            r"^0?(?P<quarter>[1-4])(?:\.|er|e|ème|º|°)?\s*(?:quarter|qtr|quartal|trimestre)\s+(?P<year>\d{4})$",
+            r"^(?:quarter|qtr|quartal|trimestre)\s*(?P<quarter>[1-4])\s+(?P<year>\d{4})$",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(&format!("(?i){}", pattern)).unwrap())
+        .collect()
+    })
+}
+
+/// Handle quarters, imputing a month within the quarter and then the day
+///
+/// `First`, `Middle` and `Last` month imputation pick the first, second or
+/// third month of the quarter, and a numeric month imputation value the
+/// first month.
+fn handle_quarter_date(
+    cleaned_date: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    strict: bool,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    let Some(caps) = quarter_regexes()
+        .iter()
+        .find_map(|regex| regex.captures(cleaned_date))
+    else {
+        return Ok(None);
+    };
+    let input = cleaned_date.to_string();
+    let quarter = caps["quarter"].parse::<i32>().unwrap();
+    let year = &caps["year"];
+    if strict && year.len() == 2 {
+        return Err(DateFixError::TwoDigitYear {
+            year: year.to_string(),
+            input: String::new(),
+        });
+    }
+    let year = year_prefix(year).parse::<i32>().unwrap();
+
+    let first_month = (quarter - 1) * 3 + 1;
+    let month = match month_impute {
+        Imputation::Na => return Err(DateFixError::NaImputed { input }),
+        Imputation::Error => return Err(DateFixError::MissingMonth { input }),
+        Imputation::First | Imputation::Value(_) => first_month,
+        Imputation::Middle => first_month + 1,
+        Imputation::Last => first_month + 2,
+    };
+    let day = match day_impute {
+        Imputation::Na => return Err(DateFixError::NaImputed { input }),
+        Imputation::Error => return Err(DateFixError::MissingDay { input }),
+        day_impute => day_impute.day(year, month).unwrap(),
+    };
+    // A numeric day imputation past the end of the month is lowered to it
+    let day = day.min(days_in_month(year, month));
+
+    let mut parsed = ParsedDate::new(year, month, day, DateSource::Quarter);
+    parsed.day_imputed = true;
+    parsed.month_imputed = true;
+    parsed.format = Some("yq");
+    Ok(Some(parsed))
+}
+
+/// Decades: "1990s", "1990's", "the 90s", "années 90", "los años 90",
+/// "anos 90" and "1990er Jahre" once cleaned
+fn decade_regexes() -> &'static [Regex] {
+    static DECADE_REGEXES: OnceLock<Vec<Regex>> = OnceLock::new();
+    DECADE_REGEXES.get_or_init(|| {
+        [
+            r"^(?:the\s+)?'?(?P<decade>\d{3}0|\d0)'?s$",
+            r"^(?:les\s+|des\s+)?ann[ée]es\s+(?P<decade>\d{3}0|\d0)$",
+            r"^(?:los\s+)?años\s+(?P<decade>\d{3}0|\d0)$",
+            r"^(?:os\s+)?anos\s+(?P<decade>\d{3}0|\d0)$",
+            r"^(?:die\s+)?(?P<decade>\d{3}0|\d0)er(?:\s+jahre)?$",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(&format!("(?i){}", pattern)).unwrap())
+        .collect()
+    })
+}
+
+/// Handle decades, imputing a year within the decade with `impute_year_span`
+///
+/// Two digit decades such as "the 90s" are given a century as two digit
+/// years are.
+fn handle_decade_date(
+    cleaned_date: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    strict: bool,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    let Some(caps) = decade_regexes()
+        .iter()
+        .find_map(|regex| regex.captures(cleaned_date))
+    else {
+        return Ok(None);
+    };
+    let decade = &caps["decade"];
+    if strict && decade.len() == 2 {
+        return Err(DateFixError::TwoDigitYear {
+            year: decade.to_string(),
+            input: String::new(),
+        });
+    }
+    let decade = year_prefix(decade).parse::<i32>().unwrap();
+    impute_year_span(
+        decade,
+        decade + 9,
+        DateSource::Decade,
+        cleaned_date,
+        day_impute,
+        month_impute,
+        strict,
+    )
+    .map(Some)
+}
+
+/// Season names in the supported languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+static SEASON_NAMES: &[(Season, &[&str])] = &[
+    (
+        Season::Spring,
+        &[
+            "spring", "printemps", "frühling", "fruhling", "frühjahr", "fruhjahr", "primavera",
+            "весна", "весной", "musim semi",
+        ],
+    ),
+    (
+        Season::Summer,
+        &[
+            "summer", "été", "ete", "sommer", "verano", "verão", "verao", "лето", "летом",
+            "musim panas",
+        ],
+    ),
+    (
+        Season::Autumn,
+        &[
+            "autumn", "fall", "automne", "herbst", "otoño", "otono", "outono", "осень",
+            "осенью", "musim gugur",
+        ],
+    ),
+    (
+        Season::Winter,
+        &[
+            "winter", "hiver", "invierno", "inverno", "зима", "зимой", "musim dingin",
+        ],
+    ),
+];
+
+impl Season {
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        SEASON_NAMES
+            .iter()
+            .find(|(_, names)| names.contains(&name.as_str()))
+            .map(|(season, _)| *season)
+    }
+
+    /// First month of the season, using meteorological seasons
+    fn first_month(self, hemisphere: Hemisphere) -> i32 {
+        match (self, hemisphere) {
+            (Season::Spring, Hemisphere::Northern) | (Season::Autumn, Hemisphere::Southern) => 3,
+            (Season::Summer, Hemisphere::Northern) | (Season::Winter, Hemisphere::Southern) => 6,
+            (Season::Autumn, Hemisphere::Northern) | (Season::Spring, Hemisphere::Southern) => 9,
+            (Season::Winter, Hemisphere::Northern) | (Season::Summer, Hemisphere::Southern) => 12,
+        }
+    }
+}
+
+/// A season followed by a year, or by two years for a season running into
+/// the next year: "Summer 2019", "verano de 2017", "winter 2018/19"
+fn season_regex() -> &'static Regex {
+    static SEASON_REGEX: OnceLock<Regex> = OnceLock::new();
+    SEASON_REGEX.get_or_init(|| {
+        Regex::new(
+            r"^(?P<season>\p{L}+(?: \p{L}+)??)\s+(?:(?:de|del|of)\s+)?(?P<year>\d{4})(?:\s*[/-]\s*(?P<end>\d{4}|\d{2}))?$",
+        )
+        .unwrap()
+    })
+}
+
+/// Whether `date` is a season given with the two years it spans, such as
+/// "winter 2018-2019", which is a single season rather than a range of years
+pub(crate) fn is_season_span(date: &str) -> bool {
+    season_regex()
+        .captures(date.trim())
+        .is_some_and(|caps| {
+            caps.name("end").is_some() && Season::from_name(&caps["season"]).is_some()
+        })
+}
+
+/// Handle seasons, imputing a month within the season and then the day
+///
+/// Month imputation picks a month of the season as for quarters. A season
+/// running from December into the next year belongs to the year it ends in
+/// when only one year is given, so "winter 2019" starts in December 2018.
+pub(crate) fn handle_season_date(
+    cleaned_date: &str,
+    day_impute: Imputation,
+    month_impute: Imputation,
+    hemisphere: Hemisphere,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    let Some(caps) = season_regex().captures(cleaned_date) else {
+        return Ok(None);
+    };
+    let Some(season) = Season::from_name(&caps["season"]) else {
+        return Ok(None);
+    };
+    let input = cleaned_date.to_string();
+    let first_month = season.first_month(hemisphere);
+    let year = caps["year"].parse::<i32>().unwrap();
+
+    // Year in which the season starts
+    let start_year = match caps.name("end") {
+        Some(end) => {
+            let end = end.as_str();
+            let follows = if end.len() == 2 {
+                end == format!("{:02}", (year + 1) % 100)
+            } else {
+                end.parse::<i32>() == Ok(year + 1)
+            };
+            if first_month != 12 || !follows {
+                return Err(DateFixError::Unparseable { input });
+            }
+            year
+        }
+        None if first_month == 12 => year - 1,
+        None => year,
+    };
+
+    let offset = match month_impute {
+        Imputation::Na => return Err(DateFixError::NaImputed { input }),
+        Imputation::Error => return Err(DateFixError::MissingMonth { input }),
+        Imputation::First | Imputation::Value(_) => 0,
+        Imputation::Middle => 1,
+        Imputation::Last => 2,
+    };
+    let (year, month) = match first_month + offset {
+        month if month > 12 => (start_year + 1, month - 12),
+        month => (start_year, month),
+    };
+    let day = match day_impute {
+        Imputation::Na => return Err(DateFixError::NaImputed { input }),
+        Imputation::Error => return Err(DateFixError::MissingDay { input }),
+        day_impute => day_impute.day(year, month).unwrap(),
+    };
+    let day = day.min(days_in_month(year, month));
+
+    let mut parsed = ParsedDate::new(year, month, day, DateSource::Season);
+    parsed.day_imputed = true;
+    parsed.month_imputed = true;
+    parsed.format = Some("ys");
+    Ok(Some(parsed))
+}
+
+/// ISO 8601 week date: "2020-W05-3", "2020W053" or "2020-W05"
+fn iso_week_regex() -> &'static Regex {
+    static ISO_WEEK_REGEX: OnceLock<Regex> = OnceLock::new();
+    ISO_WEEK_REGEX
+        .get_or_init(|| Regex::new(r"(?i)^(\d{4})-?W(\d{2})(?:-?(\d))?$").unwrap())
+}
+
+/// ISO 8601 ordinal date: "2020-123" or "2020123"
+fn ordinal_regex() -> &'static Regex {
+    static ORDINAL_REGEX: OnceLock<Regex> = OnceLock::new();
+    ORDINAL_REGEX.get_or_init(|| Regex::new(r"^(\d{4})-?(\d{3})$").unwrap())
+}
+
+/// Handle ISO 8601 week dates, imputing the day of the week when missing
+///
+/// `First`, `Middle` and `Last` impute Monday, Thursday or Sunday, and a
+/// numeric imputation value between 1 and 7 is read as the ISO day of the
+/// week (1 is Monday).
+fn handle_iso_week_date(
+    cleaned_date: &str,
+    day_impute: Imputation,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    let Some(caps) = iso_week_regex().captures(cleaned_date) else {
+        return Ok(None);
+    };
+    let input = cleaned_date.to_string();
+    let year = caps[1].parse::<i32>().unwrap();
+    let week = caps[2].parse::<u32>().unwrap();
+    let (weekday, day_imputed) = match caps.get(3) {
+        Some(day) => (day.as_str().parse::<i32>().unwrap(), false),
+        None => match day_impute {
+            Imputation::Na => return Err(DateFixError::NaImputed { input }),
+            Imputation::Error => return Err(DateFixError::MissingDay { input }),
+            Imputation::First => (1, true),
+            Imputation::Middle => (4, true),
+            Imputation::Last => (7, true),
+            // A day of the month has no weekday, so clamp it into the week
+            Imputation::Value(day) => (day.clamp(1, 7), true),
+        },
+    };
+    let weekday = match weekday {
+        1..=7 => chrono::Weekday::try_from(weekday as u8 - 1).unwrap(),
+        _ => {
+            return Err(DateFixError::DayOutOfRange {
+                day: weekday,
+                input,
+            })
+        }
+    };
+    let date = NaiveDate::from_isoywd_opt(year, week, weekday).ok_or_else(|| {
+        DateFixError::InvalidComponent {
+            component: Component::Week,
+            value: caps[2].to_string(),
+            input: String::new(),
+        }
+    })?;
+
+    let mut parsed = ParsedDate::new(
+        date.year(),
+        date.month() as i32,
+        date.day() as i32,
+        DateSource::IsoWeek,
+    );
+    parsed.day_imputed = day_imputed;
+    parsed.format = Some(if day_imputed { "yw" } else { "ywd" });
+    Ok(Some(parsed))
+}
+
+/// Handle ISO 8601 ordinal dates, a year and the day of the year
+fn handle_ordinal_date(cleaned_date: &str) -> Result<Option<ParsedDate>, DateFixError> {
+    let Some(caps) = ordinal_regex().captures(cleaned_date) else {
+        return Ok(None);
+    };
+    let year = caps[1].parse::<i32>().unwrap();
+    let ordinal = caps[2].parse::<u32>().unwrap();
+    let date = NaiveDate::from_yo_opt(year, ordinal).ok_or(DateFixError::DayOutOfRange {
+        day: ordinal as i32,
+        input: String::new(),
+    })?;
+
+    let mut parsed = ParsedDate::new(
+        date.year(),
+        date.month() as i32,
+        date.day() as i32,
+        DateSource::Ordinal,
+    );
+    parsed.format = Some("yo");
+    Ok(Some(parsed))
+}
+
+/// Handle pure numeric dates (Excel serial dates or Unix timestamps)
+fn handle_numeric_dates(
+    cleaned_date: &str,
+    excel: bool,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    // Exclude 4-digit numbers as they're more likely to be years than timestamps
+    if is_numeric(cleaned_date) && cleaned_date.len() != 4 {
+        if let Ok(num_date) = cleaned_date.parse::<i64>() {
+            // Excel dates count from 1899-12-30 to account for Excel's 1900 leap year bug
+            let (origin, source) = if excel {
+                (NaiveDate::from_ymd_opt(1899, 12, 30), DateSource::ExcelSerial)
+            } else {
+                (NaiveDate::from_ymd_opt(1970, 1, 1), DateSource::UnixSerial)
+            };
+            return origin
+                .and_then(|origin| origin.checked_add_signed(chrono::Duration::days(num_date)))
+                .map(|result_date| {
+                    Some(ParsedDate::new(
+                        result_date.year(),
+                        result_date.month() as i32,
+                        result_date.day() as i32,
+                        source,
+                    ))
+                })
+                .ok_or_else(|| DateFixError::InvalidSerial {
+                    value: cleaned_date.to_string(),
+                    excel,
+                    input: cleaned_date.to_string(),
+                });
+        }
+    }
+    // Not a pure numeric date, continue with other parsing
+    Ok(None)
+}
+
+/// Parse a numeric date component, reporting which component was malformed
+#[inline]
+fn parse_component(value: &str, component: Component) -> Result<i32, DateFixError> {
+    value
+        .parse::<i32>()
+        .map_err(|_| DateFixError::InvalidComponent {
+            component,
+            value: value.to_string(),
+            input: String::new(),
+        })
+}
+
+/// Parse date components from date vector based on length and format
+fn parse_date_components(
+    date_vec: &[String],
+    effective_format: &str,
+    day_impute: Imputation,
+) -> Result<DateParts, DateFixError> {
+    let input = date_vec.join(" ");
+    if date_vec.len() < 3 {
+        // Handle MM/YYYY or YYYY/MM format
+        match day_impute {
+            Imputation::Error => return Err(DateFixError::MissingDay { input }),
+            // The caller reports NA imputation with a warning
+            Imputation::Na => return Err(DateFixError::NaImputed { input }),
+            _ => {}
+        }
+
+        if date_vec.len() == 2 {
+            if date_vec[0].len() == 4 {
+                // YYYY/MM
+                validate_year_length(&date_vec[0])?;
+                let year = parse_component(&date_vec[0], Component::Year)?;
+                let month = parse_component(&date_vec[1], Component::Month)?;
+                Ok((day_impute.day(year, month), Some(month), Some(year)))
+            } else if date_vec[1].len() == 4 {
+                // MM/YYYY
+                validate_year_length(&date_vec[1])?;
+                let month = parse_component(&date_vec[0], Component::Month)?;
+                let year = parse_component(&date_vec[1], Component::Year)?;
+                Ok((day_impute.day(year, month), Some(month), Some(year)))
+            } else {
+                Err(DateFixError::AmbiguousComponents { input })
+            }
+        } else {
+            Err(DateFixError::InsufficientComponents { input })
+        }
+    } else {
+        // Handle full date with day, month, year
+        if date_vec[0].len() == 4 {
+            // YYYY/MM/DD
+            validate_year_length(&date_vec[0])?;
+            let year = parse_component(&date_vec[0], Component::Year)?;
+            let month = parse_component(&date_vec[1], Component::Month)?;
+            let day = parse_component(&date_vec[2], Component::Day)?;
+            Ok((Some(day), Some(month), Some(year)))
+        } else {
+            match effective_format {
+                "mdy" => {
+                    // MM/DD/YYYY
+                    let month = parse_component(&date_vec[0], Component::Month)?;
+                    let day_str = clean_numeric_string(&date_vec[1]);
+                    let day = parse_component(day_str, Component::Day)?;
+                    validate_year_length(&date_vec[2])?;
+                    let year = parse_component(&date_vec[2], Component::Year)?;
+                    Ok((Some(day), Some(month), Some(year)))
+                }
+                _ => {
+                    // DD/MM/YYYY
+                    let day = parse_component(&date_vec[0], Component::Day)?;
+                    let month = parse_component(&date_vec[1], Component::Month)?;
+                    validate_year_length(&date_vec[2])?;
+                    let year = parse_component(&date_vec[2], Component::Year)?;
+                    Ok((Some(day), Some(month), Some(year)))
+                }
+            }
+        }
+    }
+}
+
+/// Helper function to validate year string length
+#[inline]
+fn validate_year_length(year_str: &str) -> Result<(), DateFixError> {
+    if year_str.len() > 4 {
+        Err(DateFixError::YearTooLong {
+            year: year_str.to_string(),
+            input: String::new(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Component order used by the fast path for a date it accepted
+fn fast_path_format(date: &str, day: u8, format: &str) -> &'static str {
+    if date.as_bytes().get(4) == Some(&b'-') {
+        "ymd"
+    } else if date[0..2].parse::<u8>() == date[3..5].parse::<u8>() {
+        // Both orders agree so the requested one was used
+        if format == "mdy" {
+            "mdy"
+        } else {
+            "dmy"
+        }
+    } else if date[0..2].parse::<u8>() == Ok(day) {
+        "dmy"
+    } else {
+        "mdy"
+    }
+}
+
+/// Pipeline stages; errors are reported against the original date by the caller
+///
+/// With `options.strict` no imputation, day clamping or two digit years are
+/// accepted.
+pub fn parse_date_pipeline(
+    date_str: &str,
+    options: &ParseOptions,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    if options.format == DateFormat::Auto {
+        // A lone date is read day first unless it is only valid month first
+        let dmy = parse_date_pipeline(date_str, &options.clone().format(DateFormat::Dmy));
+        if dmy.is_err() {
+            let mdy = parse_date_pipeline(date_str, &options.clone().format(DateFormat::Mdy));
+            if mdy.is_ok() {
+                return mdy;
+            }
+        }
+        return dmy;
+    }
+    trace_date_pipeline(date_str, options, &mut Trace::disabled())
+}
+
+/// `parse_date_pipeline`, recording each stage it passes through in `trace`
+pub fn trace_date_pipeline(
+    date_str: &str,
+    options: &ParseOptions,
+    trace: &mut Trace,
+) -> Result<Option<ParsedDate>, DateFixError> {
+    options.validate()?;
+    let day_impute = options.effective_day_impute();
+    let month_impute = options.effective_month_impute();
+    // `Auto` is resolved by `parse_date_pipeline` or per column by the caller
+    let format = match options.format {
+        DateFormat::Mdy => "mdy",
+        DateFormat::Dmy | DateFormat::Auto => "dmy",
+    };
+    let excel = options.excel;
+    let roman_numeral = options.roman_numeral;
+    let strict = options.strict;
+
+    // Missing-value tokens are handled by the caller, only blank dates here
+    if date_str.trim().is_empty() {
+        trace.record("blank", || {
+            (String::new(), "empty date, returned as NA".into())
+        });
+        return Ok(None);
+    }
+
+    // A trailing time of day is kept aside and the date parsed without it
+    if let Some((date_part, time)) = split_time(date_str)? {
+        trace.record("time", || {
+            (date_part.to_string(), format!("time of day {} set aside", time))
+        });
+        let parsed = trace_date_pipeline(date_part, options, trace)?;
+        return Ok(parsed.map(|p| ParsedDate {
+            time: Some(time),
+            ..p
+        }));
+    }
+
+    // A leading qualifier such as "circa" or "early" is kept aside and the
+    // date parsed without it
+    if let Some((qualifier, date_part)) = split_qualifier(date_str) {
+        trace.record("qualifier", || {
+            (
+                date_part.to_string(),
+                format!("{} qualifier set aside", qualifier.as_str()),
+            )
+        });
+        let parsed = trace_date_pipeline(date_part, options, trace)?;
+        return Ok(parsed.map(|p| qualify(p, qualifier, day_impute, month_impute)));
+    }
+
+    // Dates in the Extended Date/Time Format when asked for, before the fast
+    // path takes the plain ISO 8601 ones. Messy input such as "2019-24" is
+    // otherwise never read as EDTF
+    if options.edtf {
+        match handle_edtf_date(
+            date_str.trim(),
+            day_impute,
+            month_impute,
+            options.hemisphere,
+            strict,
+        ) {
+            Ok(Some(result)) => {
+                trace.record("edtf", || {
+                    (
+                        result.to_iso_string(),
+                        "Extended Date/Time Format date".into(),
+                    )
+                });
+                return Ok(Some(result));
+            }
+            Err(e) => {
+                trace.record("edtf", || {
+                    (
+                        date_str.to_string(),
+                        format!("Extended Date/Time Format date, {}", e),
+                    )
+                });
+                return Err(e);
+            }
+            Ok(None) => {}
+        }
+    }
+
+    // Try fast-path parsing for common formats first. Its two digit years
+    // are given a century, so strict mode only takes it for four digit years
+    let fast_path = if strict && date_str.len() != 10 {
+        None
+    } else {
+        fast_path_parse_date(date_str, format)
+    };
+    if let Some((day, month, year)) = fast_path {
+        trace.record("fast_path", || {
+            (
+                format!("day {}, month {}, year {}", day, month, year),
+                format!(
+                    "fixed-width date read as {}",
+                    fast_path_format(date_str, day, format)
+                ),
+            )
+        });
+        // Still need to validate and adjust the date components (e.g., Feb 30 -> Feb 28)
+        let (adjusted_day, adjusted_month, adjusted_year) =
+            check_output(
+                Some(day as i32),
+                Some(month as i32),
+                Some(year as i32),
+                strict,
+            )?;
+        trace_check_output(
+            trace,
+            Some(day as i32),
+            (adjusted_day, adjusted_month, adjusted_year),
+        );
+
+        let (y, m, d) = combine_partial_date(adjusted_day, adjusted_month, adjusted_year)?;
+        let mut parsed = ParsedDate::new(y, m, d, DateSource::FastPath);
+        parsed.day_clamped = d != day as i32;
+        parsed.format = Some(fast_path_format(date_str, day, format));
+        return Ok(Some(parsed));
+    }
+    trace.record("fast_path", || {
+        (date_str.to_string(), "not a fixed-width date".into())
+    });
+
+    // Clean the date string using combined approach
+    let cleaned_date = clean_date_string_combined(date_str).into_owned();
+    trace.record("clean", || {
+        let steps = cleaning_steps(date_str);
+        let detail = if steps.is_empty() {
+            "unchanged".to_string()
+        } else {
+            steps.join(", ")
+        };
+        (cleaned_date.clone(), detail)
+    });
+
+    // Try handling as year-only date
+    match handle_year_only_date(&cleaned_date, day_impute, month_impute, strict) {
+        Ok(Some(result)) => {
+            trace.record("year_only", || {
+                (
+                    result.to_iso_string(),
+                    "four digit year, month and day imputed".into(),
+                )
+            });
+            return Ok(Some(result));
+        }
+        Err(e) => {
+            trace.record("year_only", || {
+                let detail = match e {
+                    DateFixError::NaImputed { .. } => "four digit year, NA imputed",
+                    _ => "four digit year, no imputation value",
+                };
+                (cleaned_date.clone(), detail.into())
+            });
+            return Err(e);
+        }
+        // Not a year-only date, continue with normal processing
+        Ok(None) => {}
+    }
+
+    match handle_quarter_date(&cleaned_date, day_impute, month_impute, strict) {
+        Ok(Some(result)) => {
+            trace.record("quarter", || {
+                (
+                    result.to_iso_string(),
+                    "quarter, month and day imputed".into(),
+                )
+            });
+            return Ok(Some(result));
+        }
+        Err(e) => {
+            trace.record("quarter", || (cleaned_date.clone(), format!("quarter, {}", e)));
+            return Err(e);
+        }
+        Ok(None) => {}
+    }
+
+    match handle_decade_date(&cleaned_date, day_impute, month_impute, strict) {
+        Ok(Some(result)) => {
+            trace.record("decade", || {
+                (
+                    result.to_iso_string(),
+                    "decade, year, month and day imputed".into(),
+                )
+            });
+            return Ok(Some(result));
+        }
+        Err(e) => {
+            trace.record("decade", || (cleaned_date.clone(), format!("decade, {}", e)));
+            return Err(e);
+        }
+        Ok(None) => {}
+    }
+
+    match handle_season_date(&cleaned_date, day_impute, month_impute, options.hemisphere) {
+        Ok(Some(result)) => {
+            trace.record("season", || {
+                (
+                    result.to_iso_string(),
+                    "season, month and day imputed".into(),
+                )
+            });
+            return Ok(Some(result));
+        }
+        Err(e) => {
+            trace.record("season", || (cleaned_date.clone(), format!("season, {}", e)));
+            return Err(e);
+        }
+        Ok(None) => {}
+    }
+
+    // ISO 8601 week and ordinal dates come before serial numbers, as
+    // "2020123" is the 123rd day of 2020 rather than a day count
+    match handle_iso_week_date(&cleaned_date, day_impute) {
+        Ok(Some(result)) => {
+            trace.record("iso_week", || {
+                let detail = if result.day_imputed {
+                    "ISO 8601 week, day of the week imputed"
+                } else {
+                    "ISO 8601 week date"
+                };
+                (result.to_iso_string(), detail.into())
+            });
+            return Ok(Some(result));
+        }
+        Err(e) => {
+            trace.record("iso_week", || {
+                (cleaned_date.clone(), format!("ISO 8601 week date, {}", e))
+            });
+            return Err(e);
+        }
+        Ok(None) => {}
+    }
+    if let Some(result) = handle_ordinal_date(&cleaned_date)? {
+        trace.record("ordinal", || {
+            (result.to_iso_string(), "ISO 8601 year and day of the year".into())
+        });
+        return Ok(Some(result));
+    }
+
+    // Try handling as pure numeric date (Excel/Unix)
+    match handle_numeric_dates(&cleaned_date, excel) {
+        Ok(Some(result)) => {
+            trace.record("serial", || {
+                let detail = if excel {
+                    "Excel serial day count"
+                } else {
+                    "days since 1970-01-01"
+                };
+                (result.to_iso_string(), detail.into())
+            });
+            return Ok(Some(result));
+        }
+        Err(e) => {
+            trace.record("serial", || {
+                (cleaned_date.clone(), "day count outside the supported range".into())
+            });
+            return Err(e);
+        }
+        Ok(None) => {}
+    }
+
+    // Process the date string - convert to Vec<String> from Vec<&str>
+    let date_vec_str = separate_date_optimized(&cleaned_date);
+    let mut date_vec: Vec<String> = date_vec_str.into_iter().map(|s| s.to_string()).collect();
+    trace.record("separate", || {
+        (
+            show_components(&date_vec),
+            format!("split into {} component(s)", date_vec.len()),
+        )
+    });
+
+    // Check if first element is a month name (forces MDY format)
+    let effective_format = if first_is_month(&date_vec) {
+        "mdy"
+    } else {
+        format
+    };
+    trace.record("first_is_month", || {
+        let detail = if effective_format != format {
+            "month name first, reading as mdy".to_string()
+        } else {
+            format!("using format {}", format)
+        };
+        (effective_format.to_string(), detail)
+    });
+
+    // Convert text months to numbers in date components
+    let mut source = DateSource::Numeric;
+    let mut language = None;
+    let mut replaced = Vec::new();
+    for component in &mut date_vec {
+        let converted = convert_text_month_optimized(component).into_owned();
+        if converted != component.to_lowercase() {
+            source = DateSource::TextMonth;
+            language = language.or_else(|| {
+                component.split_whitespace().find_map(|word| {
+                    month_language(word.trim_matches(|c: char| !c.is_alphabetic()))
+                })
+            });
+            replaced.push(format!("\"{}\" -> \"{}\"", component, converted));
+        }
+        *component = converted;
+    }
+    trace.record("text_month", || {
+        let detail = match (replaced.is_empty(), language) {
+            (true, _) => "no month names".to_string(),
+            (false, Some(language)) => format!("{} ({})", replaced.join(", "), language),
+            (false, None) => replaced.join(", "),
+        };
+        (show_components(&date_vec), detail)
+    });
+
+    // Handle Roman numerals
+    if roman_numeral {
+        let before = date_vec.clone();
+        date_vec = roman_conversion(date_vec);
+        if date_vec != before {
+            source = DateSource::RomanNumeral;
+        }
+        trace.record("roman_numeral", || {
+            let detail = if date_vec != before {
+                format!("\"{}\" -> \"{}\"", before[1], date_vec[1])
+            } else {
+                "no Roman numeral month".to_string()
+            };
+            (show_components(&date_vec), detail)
+        });
+    }
+
+    // Check for overly long components before processing
+    if date_str.len() > 200 || date_vec.iter().any(|s| s.len() > 6) {
+        return Err(DateFixError::Unparseable {
+            input: date_str.to_string(),
+        });
+    }
+
+    // Check for components that are too long to be valid date parts
+    // Any numeric component longer than 4 digits is invalid for years, and anything over 2 digits is invalid for days/months
+    for component in &date_vec {
+        if is_numeric(component) && component.len() > 4 {
+            return Err(DateFixError::YearTooLong {
+                year: component.clone(),
+                input: date_str.to_string(),
+            });
+        }
+    }
+
+    // Reject dates with more than 3 components (more than 2 separators)
+    if date_vec.len() > 3 {
+        return Err(DateFixError::TooManyComponents {
+            components: date_vec.len(),
+            input: date_str.to_string(),
+        });
+    }
+
+    // Append year prefixes if needed
+    let before = date_vec.clone();
+    date_vec = append_year(date_vec, strict)?;
+    trace.record("append_year", || {
+        let changed: Vec<String> = before
+            .iter()
+            .zip(&date_vec)
+            .filter(|(b, a)| b != a)
+            .map(|(b, a)| format!("\"{}\" -> \"{}\"", b, a))
+            .collect();
+        let detail = if changed.is_empty() {
+            "no two digit year".to_string()
+        } else {
+            changed.join(", ")
+        };
+        (show_components(&date_vec), detail)
+    });
+
+    // Parse the date components based on length and format
+    let (day, month, year) = parse_date_components(&date_vec, effective_format, day_impute)?;
+    trace.record("components", || {
+        let show = |c: Option<i32>| c.map_or("NA".to_string(), |c| c.to_string());
+        let detail = if date_vec.len() < 3 {
+            "day imputed"
+        } else if date_vec[0].len() == 4 {
+            "year first"
+        } else {
+            effective_format
+        };
+        (
+            format!(
+                "day {}, month {}, year {}",
+                show(day),
+                show(month),
+                show(year)
+            ),
+            detail.into(),
+        )
+    });
+
+    // Validate and adjust the date components
+    let (adjusted_day, adjusted_month, adjusted_year) = check_output(day, month, year, strict)?;
+    trace_check_output(trace, day, (adjusted_day, adjusted_month, adjusted_year));
+
+    // Record how the components were read
+    let year_first = date_vec.first().is_some_and(|s| s.len() == 4);
+    let resolved_format = match (date_vec.len(), year_first) {
+        (2, true) => "ym",
+        (2, false) => "my",
+        (_, true) => "ymd",
+        _ if effective_format == "mdy" => "mdy",
+        _ => "dmy",
+    };
+
+    // Combine into final date
+    let (y, m, d) = combine_partial_date(adjusted_day, adjusted_month, adjusted_year)?;
+    Ok(Some(ParsedDate {
+        year: y,
+        month: m,
+        day: d,
+        day_imputed: date_vec.len() < 3,
+        month_imputed: false,
+        day_clamped: adjusted_day != day,
+        format: Some(resolved_format),
+        language,
+        source,
+        time: None,
+        qualifier: None,
+        uncertain: false,
+    }))
+}
+
+/// Record the outcome of `check_output` in a trace
+fn trace_check_output(trace: &mut Trace, day: Option<i32>, adjusted: DateParts) {
+    trace.record("check_output", || {
+        let show = |c: Option<i32>| c.map_or("NA".to_string(), |c| c.to_string());
+        let (adjusted_day, month, year) = adjusted;
+        let value = format!(
+            "day {}, month {}, year {}",
+            show(adjusted_day),
+            show(month),
+            show(year)
+        );
+        let detail = match (day, adjusted_day) {
+            (Some(d), Some(a)) if d != a => {
+                format!("day {} past the end of the month, lowered to {}", d, a)
+            }
+            _ => "components in range".to_string(),
+        };
+        (value, detail)
+    });
+}
+
+/// Both readings of a date whose meaning depends on the day/month order
+///
+/// Returns `None` unless the date tidies under both `"dmy"` and `"mdy"` to
+/// different dates. The readings come from `parse_date_pipeline`, so they are
+/// exactly what `fix_date_column` would return for either format.
+pub fn ambiguous_candidates(
+    date: &str,
+    excel: bool,
+    roman_numeral: bool,
+) -> Option<(ParsedDate, ParsedDate)> {
+    let options = ParseOptions::new().excel(excel).roman_numeral(roman_numeral);
+    let dmy = parse_date_pipeline(date, &options.clone().format(DateFormat::Dmy));
+    let mdy = parse_date_pipeline(date, &options.format(DateFormat::Mdy));
+    match (OrderEvidence::from_results(&dmy, &mdy), dmy, mdy) {
+        (OrderEvidence::Ambiguous, Ok(Some(dmy)), Ok(Some(mdy))) => Some((dmy, mdy)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rm_ordinal_suffixes() {
+        assert_eq!(rm_ordinal_suffixes_optimized("1st January"), "1 January");
+        assert_eq!(rm_ordinal_suffixes_optimized("2nd February"), "2 February");
+        assert_eq!(rm_ordinal_suffixes_optimized("3rd March"), "3 March");
+        assert_eq!(rm_ordinal_suffixes_optimized("4th April"), "4 April");
+    }
+
+    #[test]
+    fn test_separate_date() {
+        assert_eq!(separate_date_optimized("01/02/2020"), vec!["01", "02", "2020"]);
+        assert_eq!(separate_date_optimized("01-02-2020"), vec!["01", "02", "2020"]);
+        assert_eq!(separate_date_optimized("01 02 2020"), vec!["01", "02", "2020"]);
+        assert_eq!(
+            separate_date_optimized("01 de febrero del 2020"),
+            vec!["01", "febrero", "2020"]
+        );
+    }
+
+    #[test]
+    fn test_convert_text_month() {
+        assert_eq!(convert_text_month_optimized("january 2020"), "01 2020");
+        assert_eq!(convert_text_month_optimized("février 2020"), "02 2020");
+        assert_eq!(convert_text_month_optimized("march 2020"), "03 2020");
+        assert_eq!(convert_text_month_optimized("декабрь 2020"), "12 2020");
+        // Test Spanish months
+        assert_eq!(convert_text_month_optimized("20 abril 1994"), "20 04 1994");
+        assert_eq!(convert_text_month_optimized("06 enero 2008"), "06 01 2008");
+    }
+
+    #[test]
+    fn test_year_prefix() {
+        // Assuming current year is 2025, years <= 25 should get 20 prefix, > 25 should get 19
+        assert_eq!(year_prefix("23"), "2023");
+        assert_eq!(year_prefix("99"), "1999");
+        assert_eq!(year_prefix("2020"), "2020"); // Already 4 digits
+    }
+
+    #[test]
+    fn test_is_numeric() {
+        assert!(is_numeric("12345"));
+        assert!(!is_numeric("123a5"));
+        assert!(!is_numeric("hello"));
+        assert!(is_numeric("0"));
+    }
+
+    #[test]
+    fn test_first_is_month() {
+        assert!(first_is_month(&[
+            "january".to_string(),
+            "1".to_string(),
+            "2020".to_string()
+        ]));
+        assert!(first_is_month(&[
+            "février".to_string(),
+            "1".to_string(),
+            "2020".to_string()
+        ]));
+        assert!(!first_is_month(&[
+            "1".to_string(),
+            "january".to_string(),
+            "2020".to_string()
+        ]));
+        assert!(!first_is_month(&[
+            "32".to_string(),
+            "1".to_string(),
+            "2020".to_string()
+        ]));
+    }
+
+    #[test]
+    fn test_roman_conversion() {
+        let date_vec = vec!["1".to_string(), "ii".to_string(), "2020".to_string()];
+        let result = roman_conversion(date_vec);
+        assert_eq!(result, vec!["1", "02", "2020"]);
+
+        let date_vec = vec!["15".to_string(), "xii".to_string(), "2020".to_string()];
+        let result = roman_conversion(date_vec);
+        assert_eq!(result, vec!["15", "12", "2020"]);
+    }
+
+    #[test]
+    fn test_check_output() {
+        // Valid date
+        let result = check_output(Some(15), Some(6), Some(2020), false).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Invalid month
+        assert!(check_output(Some(15), Some(13), Some(2020), false).is_err());
+
+        // Day adjustment for February in leap year
+        let result = check_output(Some(29), Some(2), Some(2020), false).unwrap();
+        assert_eq!(result, (Some(29), Some(2), Some(2020)));
+
+        // Day adjustment for February in non-leap year
+        let result = check_output(Some(29), Some(2), Some(2021), false).unwrap();
+        assert_eq!(result, (Some(28), Some(2), Some(2021)));
+    }
+
+    #[test]
+    fn test_spanish_date_parsing() {
+        // Test Spanish date separation
+        let spanish_date = "20 de abril de 1994";
+        let separated = separate_date_optimized(spanish_date);
+        println!("Separated: {:?}", separated);
+        assert_eq!(separated, vec!["20", "abril", "1994"]);
+
+        // Test month conversion on individual components
+        let mut components = vec!["20".to_string(), "abril".to_string(), "1994".to_string()];
+        for component in &mut components {
+            let converted = convert_text_month_optimized(component).into_owned();
+            *component = converted;
+        }
+        println!("After month conversion: {:?}", components);
+        assert_eq!(components, vec!["20", "04", "1994"]);
+    }
+
+    #[test]
+    fn test_handle_year_only_date() {
+        let handle = |date, day, month| handle_year_only_date(date, day, month, false);
+
+        // Test valid year-only date with imputation
+        let result = handle("2020", Imputation::Value(15), Imputation::Value(6)).unwrap();
+        assert_eq!(result.map(|d| d.to_iso_string()), Some("2020-06-15".to_string()));
+
+        // Test year-only date with NA day imputation
+        let result = handle("2020", Imputation::Na, Imputation::Value(6));
+        assert!(matches!(result, Err(DateFixError::NaImputed { .. })));
+
+        // Test year-only date with NA month imputation
+        let result = handle("2020", Imputation::Value(15), Imputation::Na);
+        assert!(matches!(result, Err(DateFixError::NaImputed { .. })));
+
+        // Test year-only date with both NA imputation values
+        let result = handle("2020", Imputation::Na, Imputation::Na);
+        assert!(matches!(result, Err(DateFixError::NaImputed { .. })));
+
+        // Test year-only date with missing month imputation (should error)
+        let result = handle("2020", Imputation::Value(15), Imputation::Error);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Missing month"));
+
+        // Test year-only date with missing day imputation (should error)
+        let result = handle("2020", Imputation::Error, Imputation::Value(6));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Missing day"));
+
+        // Test non-year-only date (should return None to continue processing)
+        let result = handle("01/02/2020", Imputation::Value(15), Imputation::Value(6)).unwrap();
+        assert_eq!(result, None);
+
+        // Test non-numeric year-only (should return None to continue processing)
+        let result = handle("abcd", Imputation::Value(15), Imputation::Value(6)).unwrap();
+        assert_eq!(result, None);
+
+        // Test 3-digit year (should return None to continue processing)
+        let result = handle("202", Imputation::Value(15), Imputation::Value(6)).unwrap();
+        assert_eq!(result, None);
+
+        // Test 5-digit year (should return None to continue processing)
+        let result = handle("20201", Imputation::Value(15), Imputation::Value(6)).unwrap();
+        assert_eq!(result, None);
+
+        // Test edge case: year 0000
+        let result = handle("0000", Imputation::Value(1), Imputation::Value(1)).unwrap();
+        assert_eq!(result.map(|d| d.to_iso_string()), Some("0000-01-01".to_string()));
+
+        // Test edge case: year 9999
+        let result = handle("9999", Imputation::Value(31), Imputation::Value(12)).unwrap();
+        assert_eq!(result.map(|d| d.to_iso_string()), Some("9999-12-31".to_string()));
+    }
+
+    #[test]
+    fn test_year_only_day_imputation_is_clamped() {
+        let options = ParseOptions::new()
+            .day_impute(Imputation::Value(31))
+            .month_impute(Imputation::Value(2));
+        let edtf = options.clone().edtf(true);
+        for (date, options, expected) in [
+            ("2021", &options, "2021-02-28"),
+            ("1990s", &options, "1990-02-28"),
+            ("2021?", &edtf, "2021-02-28"),
+            ("202X", &edtf, "2020-02-29"),
+            ("2021-02", &edtf, "2021-02-28"),
+        ] {
+            let parsed = crate::parse(date, options).unwrap();
+            assert_eq!(parsed.to_iso_string(), expected, "{}", date);
+            assert!(parsed.day_clamped, "{}", date);
+            assert!(parsed.days_since_epoch().is_some(), "{}", date);
+        }
+
+        let march = options.clone().month_impute(Imputation::Value(3));
+        assert!(!crate::parse("2021", &march).unwrap().day_clamped);
+        let (day, month) = (Imputation::Value(31), Imputation::Value(2));
+        let result = impute_year_only(2021, "2021", day, month, true);
+        assert_eq!(result.unwrap_err().kind(), "day_out_of_range");
+    }
+
+    #[test]
+    fn test_imputation_policies() {
+        let impute = |date, day, month| {
+            let options = ParseOptions::new().day_impute(day).month_impute(month);
+            parse_date_pipeline(date, &options)
+                .unwrap()
+                .unwrap()
+                .to_iso_string()
+        };
+        use Imputation::{First, Last, Middle, Value};
+
+        assert_eq!(impute("2020", First, First), "2020-01-01");
+        assert_eq!(impute("2020", Last, Last), "2020-12-31");
+        assert_eq!(impute("2020", Middle, Middle), "2020-06-30");
+        assert_eq!(impute("2020", Middle, Value(3)), "2020-03-15");
+        assert_eq!(impute("2020", Last, Value(2)), "2020-02-29");
+        assert_eq!(impute("2020", Value(10), Last), "2020-12-10");
+        assert_eq!(impute("02/2021", Last, First), "2021-02-28");
+        assert_eq!(impute("2024-02", Last, First), "2024-02-29");
+        assert_eq!(impute("April 2020", Last, First), "2020-04-30");
+        assert_eq!(impute("April 2020", Middle, Last), "2020-04-15");
+
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2021, 13), 31);
+    }
+
+    #[test]
+    fn test_na_imputation() {
+        let na_imputed = |date: &str, day, month| {
+            let options = ParseOptions::new().day_impute(day).month_impute(month);
+            matches!(
+                parse_date_pipeline(date, &options),
+                Err(DateFixError::NaImputed { .. })
+            )
+        };
+
+        assert!(na_imputed("1994", Imputation::Value(1), Imputation::Na));
+        assert!(na_imputed("1994", Imputation::Na, Imputation::Value(7)));
+        assert!(na_imputed("1994", Imputation::Na, Imputation::Na));
+        assert!(na_imputed("04/1994", Imputation::Na, Imputation::Value(7)));
+        assert!(!na_imputed("15/04/1994", Imputation::Na, Imputation::Na));
+    }
+
+    #[test]
+    fn test_handle_numeric_dates() {
+        // Test Excel date parsing
+        let result = handle_numeric_dates("44927", true).unwrap();
+        assert!(result.is_some());
+        let date_str = result.unwrap().to_iso_string();
+        // Excel date 44927 should be around 2023 (give or take depending on Excel's epoch)
+        assert!(date_str.starts_with("202"));
+
+        // Test Unix timestamp parsing
+        let result = handle_numeric_dates("18628", false).unwrap();
+        assert!(result.is_some());
+        let date_str = result.unwrap().to_iso_string();
+        // Unix day 18628 from 1970-01-01 should be around 2021
+        assert!(date_str.starts_with("202"));
+
+        // Test small Excel date
+        let result = handle_numeric_dates("1", true).unwrap();
+        assert!(result.is_some());
+        let date_str = result.unwrap().to_iso_string();
+        assert_eq!(date_str, "1899-12-31"); // Excel day 1 = Dec 31, 1899
+
+        // Test small Unix date
+        let result = handle_numeric_dates("1", false).unwrap();
+        assert!(result.is_some());
+        let date_str = result.unwrap().to_iso_string();
+        assert_eq!(date_str, "1970-01-02"); // Unix day 1 = Jan 2, 1970
+
+        // Test zero for both systems
+        let excel_zero = handle_numeric_dates("0", true).unwrap();
+        assert!(excel_zero.is_some());
+        let unix_zero = handle_numeric_dates("0", false).unwrap();
+        assert!(unix_zero.is_some());
+        // Excel day 0 vs Unix day 0 should be different
+        assert_ne!(excel_zero.unwrap(), unix_zero.unwrap());
+
+        // Test non-numeric input (should return None to continue processing)
+        let result = handle_numeric_dates("01/02/2020", true).unwrap();
+        assert_eq!(result, None);
+
+        // Test non-numeric input with letters
+        let result = handle_numeric_dates("abc123", true).unwrap();
+        assert_eq!(result, None);
+
+        // Test empty string
+        let result = handle_numeric_dates("", true).unwrap();
+        assert_eq!(result, None);
+
+        // Test mixed alphanumeric
+        let result = handle_numeric_dates("123abc", true).unwrap();
+        assert_eq!(result, None);
+
+        // Test with spaces
+        let result = handle_numeric_dates("123 456", true).unwrap();
+        assert_eq!(result, None);
+
+        // Test very large number that might overflow
+        let result = handle_numeric_dates("999999999999999999999999999999999", true);
+        // This should either return None (parsing fails) or handle gracefully
+        assert!(result.is_ok());
+
+        // Test negative numbers (should handle gracefully)
+        let result = handle_numeric_dates("-100", true);
+        assert!(result.is_ok()); // Should not panic, might return error or None
+    }
+
+    #[test]
+    fn test_parse_date_components() {
+        // Test DMY format (DD/MM/YYYY)
+        let date_vec = vec!["15".to_string(), "06".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1)).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Test MDY format (MM/DD/YYYY)
+        let date_vec = vec!["06".to_string(), "15".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "mdy", Imputation::Value(1)).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Test YMD format (YYYY/MM/DD)
+        let date_vec = vec!["2020".to_string(), "06".to_string(), "15".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1)).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Test MM/YYYY format with day imputation
+        let date_vec = vec!["06".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(15)).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Test YYYY/MM format with day imputation
+        let date_vec = vec!["2020".to_string(), "06".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(15)).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Test insufficient components (single element) - should error
+        let date_vec = vec!["2020".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(15));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Insufficient"));
+
+        // Test missing day imputation (None) - should error
+        let date_vec = vec!["06".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Error);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Missing day"));
+
+        // Test NA day imputation - should return special error
+        let date_vec = vec!["06".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Na);
+        assert!(matches!(result, Err(DateFixError::NaImputed { .. })));
+
+        // Test invalid format names
+        assert_eq!(DateFormat::from_name("xyz"), None);
+        assert_eq!(DateFormat::from_name("auto"), Some(DateFormat::Auto));
+
+        // Test overly long year (more than 4 digits) - should error
+        let date_vec = vec!["15".to_string(), "06".to_string(), "20201".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("unable to tidy"));
+
+        // Test invalid numeric components
+        let date_vec = vec!["abc".to_string(), "06".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid"));
+
+        // Test ambiguous 2-component format (neither is 4 digits)
+        let date_vec = vec!["06".to_string(), "15".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Unable to determine"));
+
+        // Test MDY with day having trailing punctuation (should be cleaned)
+        let date_vec = vec!["06".to_string(), "15,".to_string(), "2020".to_string()];
+        let result = parse_date_components(&date_vec, "mdy", Imputation::Value(1)).unwrap();
+        assert_eq!(result, (Some(15), Some(6), Some(2020)));
+
+        // Test edge cases: year 0000
+        let date_vec = vec!["0000".to_string(), "01".to_string(), "01".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1)).unwrap();
+        assert_eq!(result, (Some(1), Some(1), Some(0)));
+
+        // Test edge cases: year 9999
+        let date_vec = vec!["31".to_string(), "12".to_string(), "9999".to_string()];
+        let result = parse_date_components(&date_vec, "dmy", Imputation::Value(1)).unwrap();
+        assert_eq!(result, (Some(31), Some(12), Some(9999)));
+    }
+
+    #[test]
+    fn test_pipeline_error_kinds() {
+        // Errors carry the offending component and the original input
+        let result = crate::parse("15 13 2020", &ParseOptions::new());
+        assert_eq!(
+            result,
+            Err(DateFixError::MonthOutOfRange {
+                month: 13,
+                input: "15 13 2020".to_string()
+            })
+        );
+
+        let result = crate::parse("40 06 2020", &ParseOptions::new());
+        assert_eq!(
+            result,
+            Err(DateFixError::DayOutOfRange {
+                day: 40,
+                input: "40 06 2020".to_string()
+            })
+        );
+
+        let result = crate::parse("01 02 03 2000", &ParseOptions::new());
+        assert!(matches!(
+            result,
+            Err(DateFixError::TooManyComponents { components: 4, .. })
+        ));
+
+        let result = crate::parse("15 06 202001", &ParseOptions::new());
+        assert_eq!(
+            result,
+            Err(DateFixError::YearTooLong {
+                year: "202001".to_string(),
+                input: "15 06 202001".to_string()
+            })
+        );
+
+        let options = ParseOptions::new().day_impute(Imputation::Error);
+        let result = crate::parse("06 2020", &options);
+        assert_eq!(
+            result,
+            Err(DateFixError::MissingDay {
+                input: "06 2020".to_string()
+            })
+        );
+
+        // Display gives the untranslated message
+        let err = DateFixError::MissingMonth {
+            input: "2020".to_string(),
+        };
+        assert_eq!(err.to_string(), "Missing month with no imputation value given");
+    }
+
+    #[test]
+    fn test_days_since_epoch() {
+        let days = |y, m, d| ParsedDate::new(y, m, d, DateSource::Numeric).days_since_epoch();
+        assert_eq!(days(1970, 1, 1), Some(0.0));
+        assert_eq!(days(2020, 6, 15), Some(18428.0));
+        assert_eq!(days(1900, 1, 1), Some(-25567.0));
+        assert_eq!(days(2023, 2, 30), None);
+    }
+
+    #[test]
+    fn test_parsed_date_provenance() {
+        let parse = |date: &str| {
+            crate::parse(date, &ParseOptions::new().roman_numeral(true)).unwrap()
+        };
+
+        let parsed = parse("2020-02-30");
+        assert_eq!(parsed.source, DateSource::FastPath);
+        assert_eq!(parsed.format, Some("ymd"));
+        assert!(parsed.day_clamped);
+        assert_eq!(parsed.to_iso_string(), "2020-02-29");
+
+        let parsed = parse("15 janvier 2020");
+        assert_eq!(parsed.source, DateSource::TextMonth);
+        assert_eq!(parsed.language, Some("french"));
+        assert_eq!(parsed.format, Some("dmy"));
+        assert!(!parsed.day_imputed);
+
+        let parsed = parse("March 2020");
+        assert_eq!(parsed.source, DateSource::TextMonth);
+        assert_eq!(parsed.language, Some("english"));
+        assert_eq!(parsed.format, Some("my"));
+        assert!(parsed.day_imputed);
+        assert!(!parsed.month_imputed);
+
+        let parsed = parse("15.xii.2020");
+        assert_eq!(parsed.source, DateSource::RomanNumeral);
+        assert_eq!(parsed.language, None);
+
+        let parsed = parse("1994");
+        assert_eq!(parsed.source, DateSource::YearOnly);
+        assert!(parsed.day_imputed && parsed.month_imputed);
+        assert_eq!(parsed.to_iso_string(), "1994-07-01");
+
+        let parsed = parse("19374");
+        assert_eq!(parsed.source, DateSource::UnixSerial);
+        assert_eq!(parsed.format, None);
+
+        let parsed = parse("15 06 2020");
+        assert_eq!(parsed.source, DateSource::Numeric);
+        assert_eq!(parsed.format, Some("dmy"));
+    }
+
+    #[test]
+    fn test_partial_iso_output() {
+        let parse = |date| {
+            parse_date_pipeline(date, &ParseOptions::new())
+                .unwrap()
+                .unwrap()
+        };
+        let partial = |date| {
+            let parsed = parse(date);
+            (parsed.to_partial_iso_string(), parsed.imputation_flag())
+        };
+
+        assert_eq!(partial("1994"), ("1994".to_string(), Some("M")));
+        assert_eq!(partial("March 2020"), ("2020-03".to_string(), Some("D")));
+        assert_eq!(partial("2020-01"), ("2020-01".to_string(), Some("D")));
+        assert_eq!(partial("15/03/2020"), ("2020-03-15".to_string(), None));
+        assert_eq!(partial("2023-02-30"), ("2023-02-28".to_string(), None));
+        assert_eq!(parse("1994").to_iso_string(), "1994-07-01");
+    }
+
+    #[test]
+    fn test_trailing_time() {
+        let parse = |date| parse_date_pipeline(date, &ParseOptions::new()).unwrap().unwrap();
+
+        let parsed = parse("2020-03-15 14:30");
+        assert_eq!(parsed.to_iso_string(), "2020-03-15");
+        assert_eq!(parsed.source, DateSource::FastPath);
+        assert_eq!(parse("15/03/2020 2:30 PM").to_iso_string(), "2020-03-15");
+        assert_eq!(parse("March 15, 2020 9:00 am").to_iso_string(), "2020-03-15");
+        assert_eq!(parse("03/2020 08:00").to_iso_string(), "2020-03-01");
+
+        // 2020-03-15 is 18336 days after 1970-01-01
+        let day = 18336.0 * 86_400.0;
+        assert_eq!(parse("2020-03-15").seconds_since_epoch(), Some(day));
+        assert_eq!(
+            parse("2020-03-15T14:30:00Z").seconds_since_epoch(),
+            Some(day + 52_200.0)
+        );
+        assert_eq!(
+            parse("2020-03-15T14:30:00+01:00").seconds_since_epoch(),
+            Some(day + 48_600.0)
+        );
+
+        assert_eq!(
+            parse_date_pipeline("2020-03-15 25:00", &ParseOptions::new())
+                .unwrap_err()
+                .kind(),
+            "invalid_component"
+        );
+    }
+
+    #[test]
+    fn test_iso_week_and_ordinal_dates() {
+        let iso = |date, options: &ParseOptions| {
+            parse_date_pipeline(date, options).map(|p| p.unwrap().to_iso_string())
+        };
+        let options = ParseOptions::new();
+
+        assert_eq!(iso("2020-W05-3", &options).unwrap(), "2020-01-29");
+        assert_eq!(iso("2020W053", &options).unwrap(), "2020-01-29");
+        assert_eq!(iso("2020-w05-3", &options).unwrap(), "2020-01-29");
+        // Week 1 of 2020 starts in 2019
+        assert_eq!(iso("2020-W01-1", &options).unwrap(), "2019-12-30");
+        assert_eq!(iso("2020-123", &options).unwrap(), "2020-05-02");
+        assert_eq!(iso("2020123", &options).unwrap(), "2020-05-02");
+        assert_eq!(iso("2019-365", &options).unwrap(), "2019-12-31");
+
+        // Week only, the day of the week comes from the day imputation
+        assert_eq!(iso("2020-W05", &options).unwrap(), "2020-01-27");
+        let last = options.clone().day_impute(Imputation::Last);
+        assert_eq!(iso("2020-W05", &last).unwrap(), "2020-02-02");
+        let middle = options.clone().day_impute(Imputation::Middle);
+        assert_eq!(iso("2020-W05", &middle).unwrap(), "2020-01-30");
+
+        let week = parse_date_pipeline("2020-W05", &options).unwrap().unwrap();
+        assert_eq!(week.precision(), Precision::Week);
+        assert_eq!(week.to_partial_iso_string(), "2020-W05");
+        let (lower, upper) = week.interval();
+        assert_eq!(lower.to_iso_string(), "2020-01-27");
+        assert_eq!(upper.to_iso_string(), "2020-02-02");
+
+        let kind = |date, options: &ParseOptions| iso(date, options).unwrap_err().kind();
+        let no_day = options.clone().day_impute(Imputation::Error);
+        assert_eq!(kind("2020-W05", &no_day), "missing_day");
+        let day_3 = options.clone().day_impute(Imputation::Value(3));
+        assert_eq!(iso("2020-W05", &day_3).unwrap(), "2020-01-29");
+        // Numeric imputations past the end of the week take its last day
+        let day_15 = options.clone().day_impute(Imputation::Value(15));
+        assert_eq!(iso("2020-W05", &day_15).unwrap(), "2020-02-02");
+        assert_eq!(iso("2020-W05-3", &day_15).unwrap(), "2020-01-29");
+        assert_eq!(kind("2020-W05-8", &options), "day_out_of_range");
+        assert_eq!(kind("2021-W53-1", &options), "invalid_component");
+        assert_eq!(kind("2019-366", &options), "day_out_of_range");
+    }
+
+    #[test]
+    fn test_quarters() {
+        let iso = |date, options: &ParseOptions| {
+            parse_date_pipeline(date, options).map(|p| p.unwrap().to_iso_string())
+        };
+        let options = ParseOptions::new()
+            .day_impute(Imputation::First)
+            .month_impute(Imputation::First);
+
+        for date in [
+            "Q3 2020",
+            "q3-2020",
+            "2020Q3",
+            "2020-Q3",
+            "3Q20",
+            "T3 2020",
+            "3. Quartal 2020",
+            "3rd quarter 2020",
+            "3e trimestre 2020",
+        ] {
+            assert_eq!(iso(date, &options).unwrap(), "2020-07-01", "{}", date);
+        }
+
+        let middle = options
+            .clone()
+            .day_impute(Imputation::Middle)
+            .month_impute(Imputation::Middle);
+        assert_eq!(iso("Q1 2020", &middle).unwrap(), "2020-02-15");
+        let last = options
+            .clone()
+            .day_impute(Imputation::Last)
+            .month_impute(Imputation::Last);
+        assert_eq!(iso("Q1 2020", &last).unwrap(), "2020-03-31");
+        assert_eq!(iso("Q4 2020", &ParseOptions::new()).unwrap(), "2020-10-01");
+
+        let quarter = parse_date_pipeline("Q1 2020", &options).unwrap().unwrap();
+        assert_eq!(quarter.precision(), Precision::Quarter);
+        assert_eq!(quarter.to_partial_iso_string(), "2020");
+        let (lower, upper) = quarter.interval();
+        assert_eq!(lower.to_iso_string(), "2020-01-01");
+        assert_eq!(upper.to_iso_string(), "2020-03-31");
+
+        let kind = |date, options: &ParseOptions| iso(date, options).unwrap_err().kind();
+        let no_month = options.clone().month_impute(Imputation::Error);
+        assert_eq!(kind("Q3 2020", &no_month), "missing_month");
+        let na_day = options.clone().day_impute(Imputation::Na);
+        assert_eq!(kind("Q3 2020", &na_day), "na_imputed");
+        assert_eq!(kind("3Q20", &options.clone().strict(true)), "two_digit_year");
+        assert_eq!(kind("Q5 2020", &options), "invalid_component");
+    }
+
+    #[test]
+    fn test_seasons() {
+        let options = ParseOptions::new()
+            .day_impute(Imputation::First)
+            .month_impute(Imputation::First);
+        let south = options.clone().hemisphere(Hemisphere::Southern);
+        let iso = |date, options: &ParseOptions| {
+            parse_date_pipeline(date, options).map(|p| p.unwrap().to_iso_string())
+        };
+
+        assert_eq!(iso("Summer 2019", &options).unwrap(), "2019-06-01");
+        assert_eq!(iso("printemps 2020", &options).unwrap(), "2020-03-01");
+        assert_eq!(iso("Herbst 2021", &options).unwrap(), "2021-09-01");
+        assert_eq!(iso("verano 2017", &options).unwrap(), "2017-06-01");
+        assert_eq!(iso("verão de 2017", &options).unwrap(), "2017-06-01");
+        assert_eq!(iso("лето 2019", &options).unwrap(), "2019-06-01");
+        assert_eq!(iso("musim gugur 2019", &options).unwrap(), "2019-09-01");
+        assert_eq!(iso("verano 2017", &south).unwrap(), "2016-12-01");
+        assert_eq!(iso("winter 2019", &south).unwrap(), "2019-06-01");
+
+        // Winter runs into the next year
+        assert_eq!(iso("winter 2018/19", &options).unwrap(), "2018-12-01");
+        assert_eq!(iso("hiver 2018-2019", &options).unwrap(), "2018-12-01");
+        assert_eq!(iso("winter 2019", &options).unwrap(), "2018-12-01");
+        let last = options
+            .clone()
+            .day_impute(Imputation::Last)
+            .month_impute(Imputation::Last);
+        assert_eq!(iso("winter 2018/19", &last).unwrap(), "2019-02-28");
+        assert_eq!(iso("Summer 2019", &last).unwrap(), "2019-08-31");
+
+        let winter = parse_date_pipeline("winter 2019/20", &options).unwrap().unwrap();
+        assert_eq!(winter.precision(), Precision::Season);
+        assert_eq!(winter.to_partial_iso_string(), "2019/2020");
+        let (lower, upper) = winter.interval();
+        assert_eq!(lower.to_iso_string(), "2019-12-01");
+        assert_eq!(upper.to_iso_string(), "2020-02-29");
+        let summer = parse_date_pipeline("summer 2019", &last).unwrap().unwrap();
+        assert_eq!(summer.to_partial_iso_string(), "2019");
+        assert_eq!(summer.interval().0.to_iso_string(), "2019-06-01");
+
+        let kind = |date, options: &ParseOptions| iso(date, options).unwrap_err().kind();
+        assert_eq!(kind("summer 2018/19", &options), "unparseable");
+        assert_eq!(kind("winter 2018/20", &options), "unparseable");
+        let no_month = options.clone().month_impute(Imputation::Error);
+        assert_eq!(kind("summer 2019", &no_month), "missing_month");
+    }
+
+    #[test]
+    fn test_decades() {
+        let options = ParseOptions::new()
+            .day_impute(Imputation::First)
+            .month_impute(Imputation::First);
+        let iso = |date, options: &ParseOptions| {
+            parse_date_pipeline(date, options).map(|p| p.unwrap().to_iso_string())
+        };
+
+        for date in ["1990s", "1990's", "the 90s", "the '90s", "années 90", "los años 90"] {
+            assert_eq!(iso(date, &options).unwrap(), "1990-01-01", "{}", date);
+        }
+        assert_eq!(iso("1980er Jahre", &options).unwrap(), "1980-01-01");
+        let middle = options
+            .clone()
+            .day_impute(Imputation::Middle)
+            .month_impute(Imputation::Middle);
+        assert_eq!(iso("1990s", &middle).unwrap(), "1994-06-30");
+
+        let decade = parse_date_pipeline("1990s", &options).unwrap().unwrap();
+        assert_eq!(decade.precision(), Precision::Decade);
+        assert_eq!(decade.to_partial_iso_string(), "1990/1999");
+        assert_eq!(decade.imputation_flag(), Some("Y"));
+        assert_eq!(decade.interval().1.to_iso_string(), "1999-12-31");
+
+        let strict = options.clone().strict(true);
+        assert_eq!(iso("the 90s", &strict).unwrap_err().kind(), "two_digit_year");
+        assert_eq!(iso("1995s", &options).unwrap_err().kind(), "insufficient_components");
+    }
+
+    #[test]
+    fn test_qualifiers() {
+        let options = ParseOptions::new()
+            .day_impute(Imputation::First)
+            .month_impute(Imputation::First);
+        let parse = |date| parse_date_pipeline(date, &options).unwrap().unwrap();
+        let interval = |date| {
+            let (lower, upper) = parse(date).interval();
+            (lower.to_iso_string(), upper.to_iso_string())
+        };
+        let expect = |lower: &str, upper: &str| (lower.to_string(), upper.to_string());
+
+        let circa = parse("circa 1985");
+        assert_eq!(circa.qualifier, Some(Qualifier::Circa));
+        assert!(circa.approximate());
+        assert_eq!(circa.to_iso_string(), "1985-01-01");
+        assert_eq!(interval("ca. 1985"), expect("1985-01-01", "1985-12-31"));
+        assert_eq!(parse("~15/03/2020").to_iso_string(), "2020-03-15");
+        assert!(!parse("1985").approximate());
+
+        assert_eq!(parse("late 1990s").to_iso_string(), "1997-01-01");
+        assert_eq!(interval("late 1990s"), expect("1997-01-01", "1999-12-31"));
+        assert_eq!(interval("early 2000s"), expect("2000-01-01", "2003-12-31"));
+        assert_eq!(interval("mid 2020"), expect("2020-05-01", "2020-08-31"));
+        assert_eq!(parse("mid-May 2020").to_iso_string(), "2020-05-11");
+        assert_eq!(interval("mid-May 2020"), expect("2020-05-11", "2020-05-20"));
+        assert_eq!(interval("late Feb 2020"), expect("2020-02-21", "2020-02-29"));
+        assert_eq!(interval("late summer 2019"), expect("2019-08-01", "2019-08-31"));
+        assert_eq!(interval("fin de 2020"), expect("2020-09-01", "2020-12-31"));
+        assert_eq!(
+            interval("début des années 90"),
+            expect("1990-01-01", "1993-12-31")
+        );
+        // A full date is not narrowed
+        assert_eq!(interval("early 15/03/2020"), expect("2020-03-15", "2020-03-15"));
+
+        let last = options
+            .clone()
+            .day_impute(Imputation::Last)
+            .month_impute(Imputation::Last);
+        let iso = |date| parse_date_pipeline(date, &last).unwrap().unwrap().to_iso_string();
+        assert_eq!(iso("early 2020"), "2020-04-30");
+        assert_eq!(iso("late May 2020"), "2020-05-31");
+        assert_eq!(iso("mid 1990s"), "1996-12-31");
+    }
+
+    #[test]
+    fn test_edtf() {
+        let options = ParseOptions::new()
+            .day_impute(Imputation::First)
+            .month_impute(Imputation::First)
+            .edtf(true);
+        let parse = |date| parse_date_pipeline(date, &options).unwrap().unwrap();
+        let edtf = |date| parse(date).to_edtf_string();
+
+        let uncertain = parse("2004?");
+        assert!(uncertain.uncertain);
+        assert!(!uncertain.approximate());
+        assert_eq!(uncertain.source, DateSource::Edtf);
+        assert_eq!(uncertain.to_iso_string(), "2004-01-01");
+        let both = parse("2004-06-11%");
+        assert!(both.uncertain && both.approximate());
+        assert_eq!(parse("2004-06~").qualifier, Some(Qualifier::Circa));
+
+        assert_eq!(parse("2004-06-XX").precision(), Precision::Month);
+        assert_eq!(parse("201X").precision(), Precision::Decade);
+        let century = parse("19XX");
+        assert_eq!(century.precision(), Precision::Century);
+        assert_eq!(century.to_partial_iso_string(), "1900/1999");
+        assert_eq!(century.interval().1.to_iso_string(), "1999-12-31");
+        assert_eq!(parse("2001-21").interval().0.to_iso_string(), "2001-03-01");
+        assert_eq!(parse("-0044-03-15").year, -44);
+        // Plain ISO 8601 dates are left to the other stages
+        assert_eq!(parse("2004-06-11").source, DateSource::FastPath);
+
+        assert_eq!(edtf("15/03/2020"), "2020-03-15");
+        assert_eq!(edtf("March 2020"), "2020-03");
+        assert_eq!(edtf("2004-06-XX"), "2004-06");
+        assert_eq!(edtf("1990s"), "199X");
+        assert_eq!(edtf("20XX"), "20XX");
+        assert_eq!(edtf("2004%"), "2004%");
+        assert_eq!(edtf("circa 1985"), "1985~");
+        assert_eq!(edtf("late 1990s"), "1997~/1999~");
+        assert_eq!(edtf("Q3 2020"), "2020-07/2020-09");
+        assert_eq!(edtf("2020-W05"), "2020-01-27/2020-02-02");
+        assert_eq!(edtf("-0044-03-15"), "-0044-03-15");
+        assert_eq!(edtf("2020-03-15 14:30"), "2020-03-15T14:30:00");
+
+        let error = |date| parse_date_pipeline(date, &options).unwrap_err().kind();
+        assert_eq!(error("2004-XX-15"), "unparseable");
+        assert_eq!(error("201X-06"), "unparseable");
+        assert_eq!(error("Y170000002"), "invalid_component");
+
+        // Without the option, messy input is never read as EDTF
+        let messy = ParseOptions::new();
+        let messy_error = |date| parse_date_pipeline(date, &messy).unwrap_err().kind();
+        assert_eq!(messy_error("2019-24"), "month_out_of_range");
+        assert_eq!(messy_error("2020-21"), "month_out_of_range");
+        assert_eq!(messy_error("-2020"), "invalid_component");
+        assert_eq!(messy_error("2004?"), "insufficient_components");
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = |date| {
+            let (lower, upper) = parse_date_pipeline(date, &ParseOptions::new())
+                .unwrap()
+                .unwrap()
+                .interval();
+            (lower.to_iso_string(), upper.to_iso_string())
+        };
+        let expect = |lower: &str, upper: &str| (lower.to_string(), upper.to_string());
+
+        assert_eq!(interval("2019"), expect("2019-01-01", "2019-12-31"));
+        assert_eq!(interval("Feb 2020"), expect("2020-02-01", "2020-02-29"));
+        assert_eq!(interval("02/2021"), expect("2021-02-01", "2021-02-28"));
+        assert_eq!(interval("1900-02"), expect("1900-02-01", "1900-02-28"));
+        assert_eq!(interval("15/03/2020"), expect("2020-03-15", "2020-03-15"));
+    }
+
+    #[test]
+    fn test_precision() {
+        let precision = |date| {
+            parse_date_pipeline(date, &ParseOptions::new())
+                .unwrap()
+                .unwrap()
+                .precision()
+        };
+
+        assert_eq!(precision("2020"), Precision::Year);
+        assert_eq!(precision("March 2020"), Precision::Month);
+        assert_eq!(precision("03/2020"), Precision::Month);
+        assert_eq!(precision("2020-03"), Precision::Month);
+        assert_eq!(precision("15 March 2020"), Precision::Day);
+        assert_eq!(precision("2020-03-15"), Precision::Day);
+        assert_eq!(precision("43905"), Precision::Day);
+        assert!(Precision::Year < Precision::Month && Precision::Month < Precision::Day);
+    }
+
+    #[test]
+    fn test_validate_year_length() {
+        // Test valid 4-digit years
+        assert!(validate_year_length("2020").is_ok());
+        assert!(validate_year_length("1999").is_ok());
+        assert!(validate_year_length("0001").is_ok());
+        assert!(validate_year_length("9999").is_ok());
+        
+        // Test valid shorter years
+        assert!(validate_year_length("20").is_ok());
+        assert!(validate_year_length("1").is_ok());
+        assert!(validate_year_length("").is_ok()); // Empty is technically valid
+        
+        // Test invalid long years
+        assert!(validate_year_length("20201").is_err());
+        assert!(validate_year_length("123456").is_err());
+        
+        // Verify error message
+        let result = validate_year_length("20201");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("unable to tidy"));
+    }
+
+    #[test]
+    fn test_ambiguous_candidates() {
+        let (dmy, mdy) = ambiguous_candidates("03/04/2020", false, false).unwrap();
+        assert_eq!(dmy.to_iso_string(), "2020-04-03");
+        assert_eq!(mdy.to_iso_string(), "2020-03-04");
+
+        let (dmy, mdy) = ambiguous_candidates("1.2.98", false, false).unwrap();
+        assert_eq!(dmy.to_iso_string(), "1998-02-01");
+        assert_eq!(mdy.to_iso_string(), "1998-01-02");
+
+        // Same date either way, only valid in one order, or not a dmy/mdy date
+        assert_eq!(ambiguous_candidates("05/05/2020", false, false), None);
+        assert_eq!(ambiguous_candidates("25/12/2023", false, false), None);
+        assert_eq!(ambiguous_candidates("12/25/2023", false, false), None);
+        assert_eq!(ambiguous_candidates("2020-03-04", false, false), None);
+        assert_eq!(ambiguous_candidates("April 3 2020", false, false), None);
+        assert_eq!(ambiguous_candidates("04/2020", false, false), None);
+    }
+
+    #[test]
+    fn test_trace_date_pipeline() {
+        let trace_of = |date| {
+            let mut trace = Trace::enabled();
+            let result = trace_date_pipeline(date, &ParseOptions::new(), &mut trace);
+            (result, trace.steps().to_vec())
+        };
+
+        let (result, steps) = trace_of("July 4th, 76");
+        assert_eq!(result.unwrap().unwrap().to_iso_string(), "1976-07-04");
+        let stages: Vec<&str> = steps.iter().map(|s| s.stage).collect();
+        assert_eq!(
+            stages,
+            [
+                "fast_path",
+                "clean",
+                "separate",
+                "first_is_month",
+                "text_month",
+                "append_year",
+                "components",
+                "check_output"
+            ]
+        );
+        assert_eq!(steps[1].value, "July 4, 76");
+        assert_eq!(steps[1].detail, "ordinal_suffix");
+        assert_eq!(steps[2].value, "\"July\", \"4,\", \"76\"");
+        assert_eq!(steps[3].detail, "month name first, reading as mdy");
+        assert_eq!(steps[4].detail, "\"July\" -> \"07\" (english)");
+        assert_eq!(steps[5].detail, "\"76\" -> \"1976\"");
+
+        let (_, steps) = trace_of("30/02/2020");
+        assert_eq!(steps[0].stage, "fast_path");
+        assert_eq!(steps[0].value, "day 30, month 2, year 2020");
+        assert_eq!(
+            steps[1].detail,
+            "day 30 past the end of the month, lowered to 29"
+        );
+
+        // Tracing does not change the result
+        for date in ["15 13 2020", "2020", "01.XII.1999", "44197"] {
+            let (result, _) = trace_of(date);
+            assert_eq!(
+                result,
+                parse_date_pipeline(date, &ParseOptions::new())
+            );
+        }
+        assert!(Trace::disabled().steps().is_empty());
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let strict = |date| parse_date_pipeline(date, &ParseOptions::new().strict(true));
+
+        assert_eq!(
+            strict("25/12/2020").unwrap().unwrap().to_iso_string(),
+            "2020-12-25"
+        );
+        assert_eq!(
+            strict("July 4th, 1776").unwrap().unwrap().to_iso_string(),
+            "1776-07-04"
+        );
+
+        // No clamping, on either path
+        assert!(matches!(
+            strict("2021-02-31"),
+            Err(DateFixError::DayOutOfRange { day: 31, .. })
+        ));
+        assert!(matches!(
+            strict("31 02 2021"),
+            Err(DateFixError::DayOutOfRange { day: 31, .. })
+        ));
+
+        // No imputation, even when imputation values are given
+        assert!(matches!(strict("2021"), Err(DateFixError::MissingMonth { .. })));
+        assert!(matches!(strict("March 2021"), Err(DateFixError::MissingDay { .. })));
+
+        // No two digit years
+        assert!(matches!(
+            strict("25/12/20"),
+            Err(DateFixError::TwoDigitYear { .. })
+        ));
+        assert!(matches!(
+            strict("1.2.98"),
+            Err(DateFixError::TwoDigitYear { .. })
+        ));
+        assert_eq!(strict("1.2.98").unwrap_err().kind(), "two_digit_year");
+
+        assert_eq!(check_output(Some(31), Some(2), Some(2021), false), Ok((Some(28), Some(2), Some(2021))));
+        assert!(check_output(Some(31), Some(2), Some(2021), true).is_err());
+    }
+}
@@ -0,0 +1,327 @@
+use crate::approx::is_qualifier;
+use crate::edtf::{split_edtf_interval, OPEN_END};
+use crate::error::DateFixError;
+use crate::optimizations::{clean_date_string_combined, separate_date_optimized};
+use crate::options::ParseOptions;
+use crate::parsed::ParsedDate;
+use crate::pipeline::{is_season_span, parse_date_pipeline};
+
+/// Words and dashes which always separate the two sides of a range
+///
+/// A bare hyphen is handled separately by `split_range` as it also separates
+/// the components of a single date.
+static RANGE_SEPARATORS: &[&str] = &[
+    " to ", " until ", " au ", " bis ", " hasta ", " até ", "–", "—", " - ",
+];
+
+/// Whether a component is a four digit year
+#[inline]
+fn is_year(component: &str) -> bool {
+    component.len() == 4 && component.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Kind of a date component, used to match up the two sides of a range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComponentKind {
+    Year,
+    Number,
+    Word,
+}
+
+fn component_kind(component: &str) -> ComponentKind {
+    if is_year(component) {
+        ComponentKind::Year
+    } else if component.chars().all(|c| c.is_ascii_digit()) {
+        ComponentKind::Number
+    } else {
+        ComponentKind::Word
+    }
+}
+
+/// Byte offsets of the first case-insensitive match of `needle` in
+/// `haystack`
+///
+/// Compares lowercased characters rather than lowercasing `haystack`, so the
+/// offsets stay valid for the original string even for non-ASCII text such
+/// as "ATÉ".
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut rest = haystack[start..].chars();
+        let mut end = start;
+        for expected in needle.chars() {
+            let c = rest.next()?;
+            if !c.to_lowercase().eq(expected.to_lowercase()) {
+                return None;
+            }
+            end += c.len_utf8();
+        }
+        Some((start, end))
+    })
+}
+
+/// Cleaned components of one side of a range
+fn side_components(side: &str) -> Vec<String> {
+    let cleaned = clean_date_string_combined(side);
+    separate_date_optimized(&cleaned)
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Complete a side without a year with the components of the other side of
+/// kinds it lacks
+///
+/// "March" in "March-April 2020" becomes "March 2020", "1" in
+/// "1-15 March 2020" becomes "1 March 2020" and "July" in
+/// "May 2020 until July" becomes "July 2020".
+fn inherit(side: &str, other: &str) -> String {
+    let components = side_components(side);
+    if components.iter().any(|c| is_year(c)) {
+        return side.to_string();
+    }
+    let kinds: Vec<ComponentKind> = components.iter().map(|c| component_kind(c)).collect();
+    let missing: Vec<String> = side_components(other)
+        .into_iter()
+        .filter(|c| !kinds.contains(&component_kind(c)))
+        .collect();
+    if missing.is_empty() {
+        return side.to_string();
+    }
+    components.into_iter().chain(missing).collect::<Vec<_>>().join(" ")
+}
+
+/// Split a date range such as "March–April 2020" or
+/// "01/02/2020 to 15/02/2020" into its two sides
+///
+/// Returns `None` for a single date. A bare hyphen only separates a range
+/// when both sides start with the same kind of component, as in
+/// "2019-2021" or "1-15 March 2020", so "2020-03", "15-Mar 2020" and
+/// "Mar 15-20 2020" are single dates. A side without a year takes its missing
+/// month and year from the other.
+/// A season spanning two years, such as "winter 2018-2019", is not a range.
+/// With `options.edtf`, EDTF intervals and sets are split with
+/// `split_edtf_interval`, so their sides may be open or unknown.
+pub fn split_range(date: &str, options: &ParseOptions) -> Option<(String, String)> {
+    if options.edtf {
+        if let Some(sides) = split_edtf_interval(date) {
+            return Some(sides);
+        }
+    }
+    if is_season_span(date) {
+        return None;
+    }
+    let split = RANGE_SEPARATORS
+        .iter()
+        .find_map(|sep| find_ignore_case(date, sep))
+        .or_else(|| {
+            if date.matches('-').count() != 1 {
+                return None;
+            }
+            let i = date.find('-').unwrap();
+            let (start, end) = (date[..i].trim(), date[i + 1..].trim());
+            if is_qualifier(start) {
+                // A qualifier such as "mid" in "mid-May 2020"
+                return None;
+            }
+            // Sides of different shapes are the components of one date, as
+            // in "2020-03", "Mar-2020" or "15-Mar 2020"
+            let leading_kind =
+                |side: &str| side_components(side).first().map(|c| component_kind(c));
+            if leading_kind(start).is_none() || leading_kind(start) != leading_kind(end) {
+                return None;
+            }
+            Some((i, i + 1))
+        })?;
+
+    let (start, end) = (date[..split.0].trim(), date[split.1..].trim());
+    if start.is_empty() || end.is_empty() {
+        return None;
+    }
+    Some((inherit(start, end), inherit(end, start)))
+}
+
+/// First and last day of the range from `start` to `end`
+///
+/// Partial dates span their whole period, so "2019-2021" runs from
+/// 2019-01-01 to 2021-12-31.
+pub fn range_interval(
+    start: &ParsedDate,
+    end: &ParsedDate,
+) -> Result<(ParsedDate, ParsedDate), DateFixError> {
+    let (lower, _) = start.interval();
+    let (_, upper) = end.interval();
+    if (lower.year, lower.month, lower.day) > (upper.year, upper.month, upper.day) {
+        return Err(DateFixError::ReversedRange {
+            input: String::new(),
+        });
+    }
+    Ok((lower, upper))
+}
+
+/// Tidy a date or date range into the first and last day it covers
+///
+/// Each side of a range goes through `parse_date_pipeline`. Single dates
+/// give their `ParsedDate::interval`. Blank, open and unknown sides resolve
+/// to `None`.
+pub fn parse_range_pipeline(
+    date_str: &str,
+    options: &ParseOptions,
+) -> Result<Option<(ParsedDate, ParsedDate)>, DateFixError> {
+    let Some((start, end)) = split_range(date_str, options) else {
+        return Ok(parse_date_pipeline(date_str, options)?.map(|p| p.interval()));
+    };
+    let side = |side: &str| match side {
+        OPEN_END => Ok(None),
+        side => parse_date_pipeline(side, options),
+    };
+    match (side(&start)?, side(&end)?) {
+        (Some(start), Some(end)) => range_interval(&start, &end).map(Some),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Imputation;
+
+    #[test]
+    fn test_split_range() {
+        let options = ParseOptions::new();
+        let split = |date| split_range(date, &options);
+        let pair = |start: &str, end: &str| Some((start.to_string(), end.to_string()));
+
+        assert_eq!(split("2019-2021"), pair("2019", "2021"));
+        assert_eq!(
+            split("March–April 2020"),
+            pair("March 2020", "April 2020")
+        );
+        assert_eq!(
+            split("March-April 2020"),
+            pair("March 2020", "April 2020")
+        );
+        assert_eq!(
+            split("01/02/2020 to 15/02/2020"),
+            pair("01/02/2020", "15/02/2020")
+        );
+        assert_eq!(
+            split("1-15 March 2020"),
+            pair("1 March 2020", "15 March 2020")
+        );
+        assert_eq!(
+            split("1er au 15 mars 2020"),
+            pair("01 mars 2020", "15 mars 2020")
+        );
+        assert_eq!(
+            split("März bis Mai 2021"),
+            pair("März 2021", "Mai 2021")
+        );
+        assert_eq!(
+            split("enero hasta marzo 2019"),
+            pair("enero 2019", "marzo 2019")
+        );
+        assert_eq!(split("2019 — 2020"), pair("2019", "2020"));
+        assert_eq!(
+            split("May 2020 Until July"),
+            pair("May 2020", "July 2020")
+        );
+        assert_eq!(
+            split("2019 to March 2021"),
+            pair("2019", "March 2021")
+        );
+
+        assert_eq!(split("2020-03"), None);
+        assert_eq!(split("Mar-2020"), None);
+        assert_eq!(split("2020-03-15"), None);
+        assert_eq!(split("15/03/2020"), None);
+        assert_eq!(split("Toulouse"), None);
+        assert_eq!(split("winter 2018-2019"), None);
+        assert_eq!(split("mid-May 2020"), None);
+        assert_eq!(split("hiver 2018 - 19"), None);
+        assert_eq!(split("15-Mar 2020"), None);
+        assert_eq!(split("Mar 15-20 2020"), None);
+        assert_eq!(split("Mar-15 2020"), None);
+        assert_eq!(
+            split("Janeiro ATÉ Março 2020"),
+            pair("Janeiro 2020", "Março 2020")
+        );
+        assert_eq!(
+            split("mars 2020 AU avril"),
+            pair("mars 2020", "avril 2020")
+        );
+
+        // EDTF intervals and sets are only split when asked for
+        let edtf = options.clone().edtf(true);
+        assert_eq!(
+            split_range("1985-04-12/..", &edtf),
+            pair("1985-04-12", "..")
+        );
+        assert_eq!(
+            split_range("[1667,1668,1670..1672]", &edtf),
+            pair("1667", "1672")
+        );
+        assert_eq!(split("1985-04-12/.."), None);
+    }
+
+    #[test]
+    fn test_parse_range_pipeline() {
+        let options = ParseOptions::new()
+            .day_impute(Imputation::First)
+            .month_impute(Imputation::First);
+        let range = |date| {
+            parse_range_pipeline(date, &options)
+                .unwrap()
+                .map(|(lower, upper)| (lower.to_iso_string(), upper.to_iso_string()))
+        };
+        let expect = |lower: &str, upper: &str| Some((lower.to_string(), upper.to_string()));
+
+        assert_eq!(range("2019-2021"), expect("2019-01-01", "2021-12-31"));
+        assert_eq!(
+            range("March–April 2020"),
+            expect("2020-03-01", "2020-04-30")
+        );
+        assert_eq!(
+            range("01/02/2020 to 15/02/2020"),
+            expect("2020-02-01", "2020-02-15")
+        );
+        assert_eq!(range("1-15 March 2020"), expect("2020-03-01", "2020-03-15"));
+        assert_eq!(range("2020-03"), expect("2020-03-01", "2020-03-31"));
+        assert_eq!(range("15/03/2020"), expect("2020-03-15", "2020-03-15"));
+        assert_eq!(
+            range("winter 2018-2019"),
+            expect("2018-12-01", "2019-02-28")
+        );
+
+        let edtf = options.clone().edtf(true);
+        let edtf_range = |date| {
+            parse_range_pipeline(date, &edtf)
+                .unwrap()
+                .map(|(lower, upper)| (lower.to_iso_string(), upper.to_iso_string()))
+        };
+        assert_eq!(
+            edtf_range("1985-04/1985-06"),
+            expect("1985-04-01", "1985-06-30")
+        );
+        assert_eq!(edtf_range("1985-04-12/.."), None);
+
+        assert_eq!(
+            parse_range_pipeline("2021-2019", &options),
+            Err(DateFixError::ReversedRange {
+                input: String::new()
+            })
+        );
+        assert_eq!(
+            parse_range_pipeline("15-Mar 2020", &options)
+                .unwrap_err()
+                .kind(),
+            "unparseable"
+        );
+        assert_eq!(
+            parse_range_pipeline("32/01/2020 to 02/02/2020", &options)
+                .unwrap_err()
+                .kind(),
+            "day_out_of_range"
+        );
+    }
+}
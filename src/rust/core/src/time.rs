@@ -0,0 +1,201 @@
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::error::{Component, DateFixError};
+
+/// Time of day following a date, e.g. "14:30", "2:30 PM" or "14:30:00Z"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeOfDay {
+    /// Hour on the 24 hour clock
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    /// Fraction of a second in nanoseconds
+    pub nanosecond: u32,
+    /// Offset from UTC in minutes, `None` when no offset or zone was given
+    pub offset: Option<i32>,
+}
+
+impl TimeOfDay {
+    /// Seconds since midnight on the wall clock, ignoring the offset
+    pub fn seconds(&self) -> f64 {
+        (self.hour * 3600 + self.minute * 60 + self.second) as f64 + self.nanosecond as f64 / 1e9
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        match self.offset {
+            None => Ok(()),
+            Some(0) => f.write_str("Z"),
+            Some(offset) => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.abs();
+                write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+            }
+        }
+    }
+}
+
+/// A trailing time of day, separated from the date by whitespace or `T`
+fn time_regex() -> &'static Regex {
+    static TIME_REGEX: OnceLock<Regex> = OnceLock::new();
+    TIME_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?i)^(?P<date>.*?\S)(?:\s+|T)(?P<hour>\d{1,2})(?::(?P<minute>\d{2})(?::(?P<second>\d{2})(?:[.,](?P<fraction>\d{1,9}))?)?)?\s*(?P<ampm>[ap]\.?m\.?)?\s*(?P<offset>z|utc|gmt|[+-]\d{2}(?::?\d{2})?)?$",
+        )
+        .unwrap()
+    })
+}
+
+/// Parse a time component, reporting which component was malformed
+fn time_component(value: &str, component: Component, max: u32) -> Result<u32, DateFixError> {
+    match value.parse::<u32>() {
+        Ok(v) if v <= max => Ok(v),
+        _ => Err(DateFixError::InvalidComponent {
+            component,
+            value: value.to_string(),
+            input: String::new(),
+        }),
+    }
+}
+
+/// Offset in minutes from "Z", "UTC", "+05:30", "-0800" or "+01"
+fn parse_offset(offset: &str) -> Result<i32, DateFixError> {
+    let invalid = || DateFixError::InvalidComponent {
+        component: Component::Offset,
+        value: offset.to_string(),
+        input: String::new(),
+    };
+    if offset.len() <= 3 && offset.chars().all(|c| c.is_alphabetic()) {
+        return Ok(0);
+    }
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits: String = offset.chars().filter(|c| c.is_ascii_digit()).collect();
+    let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+    let minutes: i32 = digits
+        .get(2..)
+        .filter(|m| !m.is_empty())
+        .map_or(Ok(0), |m| m.parse().map_err(|_| invalid()))?;
+    if hours > 14 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(sign * (hours * 60 + minutes))
+}
+
+/// Split a trailing time of day from a date
+///
+/// Returns `Ok(None)` when `date` has no time. A bare number after the date
+/// is only read as an hour when followed by AM or PM, so two digit years
+/// such as "March 15, 20" are left alone.
+pub fn split_time(date: &str) -> Result<Option<(&str, TimeOfDay)>, DateFixError> {
+    let Some(caps) = time_regex().captures(date) else {
+        return Ok(None);
+    };
+    let ampm = caps
+        .name("ampm")
+        .map(|m| m.as_str().to_ascii_lowercase().starts_with('p'));
+    if caps.name("minute").is_none() && ampm.is_none() {
+        return Ok(None);
+    }
+
+    let hour_str = &caps["hour"];
+    let mut hour = match ampm {
+        Some(_) => time_component(hour_str, Component::Hour, 12)?,
+        None => time_component(hour_str, Component::Hour, 23)?,
+    };
+    if hour == 0 && ampm.is_some() {
+        return Err(DateFixError::InvalidComponent {
+            component: Component::Hour,
+            value: hour_str.to_string(),
+            input: String::new(),
+        });
+    }
+    match ampm {
+        Some(true) if hour < 12 => hour += 12,
+        Some(false) if hour == 12 => hour = 0,
+        _ => {}
+    }
+    let minute = caps
+        .name("minute")
+        .map_or(Ok(0), |m| time_component(m.as_str(), Component::Minute, 59))?;
+    let second = caps
+        .name("second")
+        .map_or(Ok(0), |s| time_component(s.as_str(), Component::Second, 59))?;
+    let nanosecond = caps
+        .name("fraction")
+        .map_or(0, |f| format!("{:0<9}", f.as_str()).parse().unwrap_or(0));
+    let offset = caps
+        .name("offset")
+        .map(|o| parse_offset(o.as_str()))
+        .transpose()?;
+
+    let date_part = caps.name("date").unwrap().as_str();
+    Ok(Some((
+        date_part,
+        TimeOfDay {
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+        },
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(date: &str) -> Option<(&str, String)> {
+        split_time(date)
+            .unwrap()
+            .map(|(date, time)| (date, time.to_string()))
+    }
+
+    #[test]
+    fn test_split_time() {
+        let expect = |date, time: &str| Some((date, time.to_string()));
+
+        assert_eq!(time("2020-03-15 14:30"), expect("2020-03-15", "14:30:00"));
+        assert_eq!(time("15/03/2020 2:30 PM"), expect("15/03/2020", "14:30:00"));
+        assert_eq!(
+            time("2020-03-15T14:30:00Z"),
+            expect("2020-03-15", "14:30:00Z")
+        );
+        assert_eq!(
+            time("2020-03-15T14:30:00.250+05:30"),
+            expect("2020-03-15", "14:30:00+05:30")
+        );
+        assert_eq!(
+            time("15 March 2020 09:05:10 -0800"),
+            expect("15 March 2020", "09:05:10-08:00")
+        );
+        assert_eq!(
+            time("March 15, 2020 12 am"),
+            expect("March 15, 2020", "00:00:00")
+        );
+        assert_eq!(time("1/2/2020 12:15 p.m."), expect("1/2/2020", "12:15:00"));
+
+        assert_eq!(time("2020-03-15"), None);
+        assert_eq!(time("March 15, 20"), None);
+        assert_eq!(time("15 March 2020"), None);
+    }
+
+    #[test]
+    fn test_split_time_errors() {
+        let message = |date| split_time(date).unwrap_err().to_string();
+
+        assert_eq!(message("2020-03-15 25:00"), "Invalid hour");
+        assert_eq!(message("2020-03-15 13:00 PM"), "Invalid hour");
+        assert_eq!(message("2020-03-15 10:61"), "Invalid minute");
+        assert_eq!(message("2020-03-15 10:00:60"), "Invalid second");
+        assert_eq!(message("2020-03-15 10:00+15:00"), "Invalid time zone offset");
+
+        let (_, time) = split_time("2020-03-15 10:00:01.5").unwrap().unwrap();
+        assert_eq!(time.nanosecond, 500_000_000);
+        assert_eq!(time.seconds(), 36001.5);
+    }
+}
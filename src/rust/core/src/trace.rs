@@ -0,0 +1,59 @@
+/// One pipeline stage as reported by `explain_date()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// Stable stage name, e.g. `"clean"` or `"text_month"`
+    pub stage: &'static str,
+    /// The date, or its components, after the stage ran
+    pub value: String,
+    /// What the stage did
+    pub detail: String,
+}
+
+/// Recorder threaded through the parsing pipeline
+///
+/// Disabled recorders never call the closures passed to `record`, so the
+/// normal parsing path pays nothing for tracing.
+#[derive(Debug, Default)]
+pub struct Trace {
+    steps: Option<Vec<TraceStep>>,
+}
+
+impl Trace {
+    pub fn disabled() -> Self {
+        Trace { steps: None }
+    }
+
+    pub fn enabled() -> Self {
+        Trace {
+            steps: Some(Vec::new()),
+        }
+    }
+
+    #[inline]
+    pub fn record<F>(&mut self, stage: &'static str, step: F)
+    where
+        F: FnOnce() -> (String, String),
+    {
+        if let Some(steps) = &mut self.steps {
+            let (value, detail) = step();
+            steps.push(TraceStep {
+                stage,
+                value,
+                detail,
+            });
+        }
+    }
+
+    pub fn steps(&self) -> &[TraceStep] {
+        self.steps.as_deref().unwrap_or(&[])
+    }
+}
+
+/// Show date components the way `explain_date()` reports them
+pub fn show_components<S: AsRef<str>>(components: &[S]) -> String {
+    components
+        .iter()
+        .map(|c| format!("\"{}\"", c.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
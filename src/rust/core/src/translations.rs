@@ -17,7 +17,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Mes fuera del rango esperado\n");
         m.insert("date should be a character", "la fecha debe ser un carácter");
         m.insert("unable to tidy a date", "no se puede ordenar una fecha");
-        m.insert("format should be either 'dmy' or 'mdy'", "el formato debe ser 'dmy' o 'mdy'");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "el formato debe ser 'dmy', 'mdy' o 'auto'");
         m.insert("Missing month with no imputation value given \n", "Mes faltante sin valor de imputación dado \n");
         m.insert("Missing day with no imputation value given \n", "Día faltante sin valor de imputación dado \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute debe ser un entero entre 1 y 31\n");
@@ -29,6 +29,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "NA imputado (fecha:");
         m.insert("NA imputed for subject", "NA imputado para sujeto");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "los valores de imputación deben ser numéricos, 'first', 'middle', 'last', NA o NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute debe ser numérico o uno de 'first', 'middle' o 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere debe ser 'north' o 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Año de dos dígitos no permitido en modo estricto");
+        m.insert("Invalid Excel date", "Fecha de Excel no válida");
+        m.insert("Invalid Unix timestamp date", "Fecha de marca de tiempo Unix no válida");
+        m.insert("Invalid hour", "Hora no válida");
+        m.insert("Invalid minute", "Minuto no válido");
+        m.insert("Invalid second", "Segundo no válido");
+        m.insert("Invalid time zone offset", "Desfase de zona horaria no válido");
+        m.insert("Invalid week", "Semana no válida");
+        m.insert("Date range ends before it starts", "El rango de fechas termina antes de empezar");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error debe ser 'stop', 'na' o 'collect'");
         m
     };
 
@@ -39,7 +52,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Mois hors de la plage attendue\n");
         m.insert("date should be a character", "la date doit être un caractère");
         m.insert("unable to tidy a date", "impossible de nettoyer une date");
-        m.insert("format should be either 'dmy' or 'mdy'", "le format doit être 'dmy' ou 'mdy'");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "le format doit être 'dmy', 'mdy' ou 'auto'");
         m.insert("Missing month with no imputation value given \n", "Mois manquant sans valeur d'imputation donnée \n");
         m.insert("Missing day with no imputation value given \n", "Jour manquant sans valeur d'imputation donnée \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute doit être un entier entre 1 et 31\n");
@@ -51,6 +64,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "NA imputé (date:");
         m.insert("NA imputed for subject", "NA imputé pour sujet");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "les valeurs d'imputation doivent être numériques, 'first', 'middle', 'last', NA ou NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute doit être numérique ou l'un de 'first', 'middle' ou 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere doit être 'north' ou 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Année à deux chiffres non autorisée en mode strict");
+        m.insert("Invalid Excel date", "Date Excel non valide");
+        m.insert("Invalid Unix timestamp date", "Date d'horodatage Unix non valide");
+        m.insert("Invalid hour", "Heure non valide");
+        m.insert("Invalid minute", "Minute non valide");
+        m.insert("Invalid second", "Seconde non valide");
+        m.insert("Invalid time zone offset", "Décalage de fuseau horaire non valide");
+        m.insert("Invalid week", "Semaine non valide");
+        m.insert("Date range ends before it starts", "La plage de dates se termine avant de commencer");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error doit être 'stop', 'na' ou 'collect'");
         m
     };
 
@@ -61,7 +87,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Měsíc není v očekávaném rozsahu\n");
         m.insert("date should be a character", "datum by měl být text");
         m.insert("unable to tidy a date", "nepodařilo se normalizovat datum");
-        m.insert("format should be either 'dmy' or 'mdy'", "Formát by měl být buď 'dmy' nebo 'mdy'");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "Formát by měl být 'dmy', 'mdy' nebo 'auto'");
         m.insert("Missing month with no imputation value given \n", "Chybí měsíc bez dané hodnoty imputace \n");
         m.insert("Missing day with no imputation value given \n", "Chybí den bez dané hodnoty imputace \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute by mělo být celé číslo mezi 1 a 31\n");
@@ -73,6 +99,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "Imputované NA (datum:");
         m.insert("NA imputed for subject", "Imputované NA pro subjekt");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "hodnoty imputace musí být číselné, 'first', 'middle', 'last', NA nebo NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute musí být číselné nebo jedno z 'first', 'middle' nebo 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere by mělo být 'north' nebo 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Dvoumístný rok není v přísném režimu povolen");
+        m.insert("Invalid Excel date", "Neplatné datum Excelu");
+        m.insert("Invalid Unix timestamp date", "Neplatné datum časového razítka Unix");
+        m.insert("Invalid hour", "Neplatná hodina");
+        m.insert("Invalid minute", "Neplatná minuta");
+        m.insert("Invalid second", "Neplatná sekunda");
+        m.insert("Invalid time zone offset", "Neplatný posun časového pásma");
+        m.insert("Invalid week", "Neplatný týden");
+        m.insert("Date range ends before it starts", "Rozsah dat končí dříve, než začíná");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error by mělo být 'stop', 'na' nebo 'collect'");
         m
     };
 
@@ -83,7 +122,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Monat nicht im erwarteten Bereich\n");
         m.insert("date should be a character", "date muss vom Typ character sein");
         m.insert("unable to tidy a date", "Datum kann nicht bereinigt werden");
-        m.insert("format should be either 'dmy' or 'mdy'", "format sollte entweder 'dmy' oder 'mdy' sein");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "format sollte 'dmy', 'mdy' oder 'auto' sein");
         m.insert("Missing month with no imputation value given \n", "Fehlender Monat ohne Imputationswert \n");
         m.insert("Missing day with no imputation value given \n", "Fehlender Tag ohne Imputationswert \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute sollte eine Ganzzahl zwischen 1 und 31 sein\n");
@@ -95,6 +134,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "NA imputiert (Datum:");
         m.insert("NA imputed for subject", "NA imputiert für");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "Imputationswerte müssen numerisch, 'first', 'middle', 'last', NA oder NULL sein");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute muss numerisch oder eines von 'first', 'middle' oder 'last' sein");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere sollte 'north' oder 'south' sein");
+        m.insert("Two digit year not allowed in strict mode", "Zweistellige Jahreszahl im strikten Modus nicht erlaubt");
+        m.insert("Invalid Excel date", "Ungültiges Excel-Datum");
+        m.insert("Invalid Unix timestamp date", "Ungültiges Unix-Zeitstempeldatum");
+        m.insert("Invalid hour", "Ungültige Stunde");
+        m.insert("Invalid minute", "Ungültige Minute");
+        m.insert("Invalid second", "Ungültige Sekunde");
+        m.insert("Invalid time zone offset", "Ungültiger Zeitzonenversatz");
+        m.insert("Invalid week", "Ungültige Woche");
+        m.insert("Date range ends before it starts", "Der Datumsbereich endet vor seinem Beginn");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error sollte 'stop', 'na' oder 'collect' sein");
         m
     };
 
@@ -105,7 +157,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Bulan bukan dalam jangka yang di ekspektasi\n");
         m.insert("date should be a character", "tanggal harus berupa karakter");
         m.insert("unable to tidy a date", "tanggal tidak dapat dirapihkan");
-        m.insert("format should be either 'dmy' or 'mdy'", "format harus 'dmy' atau 'mdy'");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "format harus 'dmy', 'mdy' atau 'auto'");
         m.insert("Missing month with no imputation value given \n", "Bulan hilang tanpa nilai imputasi \n");
         m.insert("Missing day with no imputation value given \n", "Hari hilang tanpa nilai imputasi \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute harus berupa bilangan bulat antara 1 dan 31\n");
@@ -117,6 +169,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "NA diperhitungkan (tanggal:");
         m.insert("NA imputed for subject", "NA diperhitungkan untuk subjek");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "nilai imputasi harus berupa numerik, 'first', 'middle', 'last', NA atau NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute harus berupa numerik atau salah satu dari 'first', 'middle' atau 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere harus 'north' atau 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Tahun dua digit tidak diizinkan dalam mode ketat");
+        m.insert("Invalid Excel date", "Tanggal Excel tidak valid");
+        m.insert("Invalid Unix timestamp date", "Tanggal stempel waktu Unix tidak valid");
+        m.insert("Invalid hour", "Jam tidak valid");
+        m.insert("Invalid minute", "Menit tidak valid");
+        m.insert("Invalid second", "Detik tidak valid");
+        m.insert("Invalid time zone offset", "Selisih zona waktu tidak valid");
+        m.insert("Invalid week", "Minggu tidak valid");
+        m.insert("Date range ends before it starts", "Rentang tanggal berakhir sebelum dimulai");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error harus salah satu dari 'stop', 'na' atau 'collect'");
         m
     };
 
@@ -127,7 +192,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Mês fora do intervalo esperado\n");
         m.insert("date should be a character", "data deve ser um caractere");
         m.insert("unable to tidy a date", "não foi possível organizar a data");
-        m.insert("format should be either 'dmy' or 'mdy'", "formato deve ser 'dmy' ou 'mdy'");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "formato deve ser 'dmy', 'mdy' ou 'auto'");
         m.insert("Missing month with no imputation value given \n", "Mês ausente sem valor de imputação \n");
         m.insert("Missing day with no imputation value given \n", "Dia ausente sem valor de imputação \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute deve ser um inteiro entre 1 e 31\n");
@@ -139,6 +204,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "NA imputado (data:");
         m.insert("NA imputed for subject", "NA imputado para sujeito");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "os valores de imputação devem ser numéricos, 'first', 'middle', 'last', NA ou NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute deve ser numérico ou um de 'first', 'middle' ou 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere deve ser 'north' ou 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Ano de dois dígitos não permitido no modo estrito");
+        m.insert("Invalid Excel date", "Data do Excel inválida");
+        m.insert("Invalid Unix timestamp date", "Data de carimbo de tempo Unix inválida");
+        m.insert("Invalid hour", "Hora inválida");
+        m.insert("Invalid minute", "Minuto inválido");
+        m.insert("Invalid second", "Segundo inválido");
+        m.insert("Invalid time zone offset", "Deslocamento de fuso horário inválido");
+        m.insert("Invalid week", "Semana inválida");
+        m.insert("Date range ends before it starts", "O intervalo de datas termina antes de começar");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error deve ser 'stop', 'na' ou 'collect'");
         m
     };
 
@@ -149,7 +227,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Месяц определен вне ожидаемого диапазона\n");
         m.insert("date should be a character", "Переменная `date` должна быть строкой");
         m.insert("unable to tidy a date", "Не удалось почистить дату");
-        m.insert("format should be either 'dmy' or 'mdy'", "переменная `format` должна быть либо 'dmy' (т.е., день-месяц-год), либо 'mdy' (т.е., месяц-день-год)");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "переменная `format` должна быть 'dmy' (т.е., день-месяц-год), 'mdy' (т.е., месяц-день-год) или 'auto'");
         m.insert("Missing month with no imputation value given \n", "Отсутствует месяц без значения для импутации \n");
         m.insert("Missing day with no imputation value given \n", "Отсутствует день без значения для импутации \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "`day.impute` должен быть целым числом между 1 и 31\n");
@@ -161,6 +239,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "Н/Д (т.е. `NA`) присвоено (дата:");
         m.insert("NA imputed for subject", "Н/Д (т.е. `NA`) присвоено для предмета");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "значения импутации должны быть числовыми, 'first', 'middle', 'last', NA или NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute должен быть числом или одним из 'first', 'middle' или 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere должен быть 'north' или 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Двузначный год не допускается в строгом режиме");
+        m.insert("Invalid Excel date", "Недопустимая дата Excel");
+        m.insert("Invalid Unix timestamp date", "Недопустимая дата в формате времени Unix");
+        m.insert("Invalid hour", "Недопустимый час");
+        m.insert("Invalid minute", "Недопустимая минута");
+        m.insert("Invalid second", "Недопустимая секунда");
+        m.insert("Invalid time zone offset", "Недопустимое смещение часового пояса");
+        m.insert("Invalid week", "Недопустимая неделя");
+        m.insert("Date range ends before it starts", "Диапазон дат заканчивается раньше, чем начинается");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error должен быть 'stop', 'na' или 'collect'");
         m
     };
 
@@ -171,7 +262,7 @@ lazy_static! {
         m.insert("Month not in expected range\n", "Mesiac nie je v očakávanom rozsahu\n");
         m.insert("date should be a character", "dátum by malo byť text");
         m.insert("unable to tidy a date", "nepodarilo sa normalizovať dátum");
-        m.insert("format should be either 'dmy' or 'mdy'", "Formát by mal byť 'dmy' alebo 'mdy'.");
+        m.insert("format should be one of 'dmy', 'mdy' or 'auto'", "Formát by mal byť 'dmy', 'mdy' alebo 'auto'.");
         m.insert("Missing month with no imputation value given \n", "Chýbajúci mesiac bez danej hodnoty imputácie \n");
         m.insert("Missing day with no imputation value given \n", "Chýbajúci deň bez danej hodnoty imputácie \n");
         m.insert("day.impute should be an integer between 1 and 31\n", "day.impute by malo byť celé číslo medzi 1 a 31\n");
@@ -183,6 +274,19 @@ lazy_static! {
         m.insert(")", ")");
         m.insert("NA imputed (date:", "Imputované NA (dátum:");
         m.insert("NA imputed for subject", "Imputované NA pre subjekt");
+        m.insert("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL", "hodnoty imputácie musia byť číselné, 'first', 'middle', 'last', NA alebo NULL");
+        m.insert("day.impute must be numeric or one of 'first', 'middle' or 'last'", "day.impute musí byť číselné alebo jedno z 'first', 'middle' alebo 'last'");
+        m.insert("hemisphere should be 'north' or 'south'", "hemisphere by malo byť 'north' alebo 'south'");
+        m.insert("Two digit year not allowed in strict mode", "Dvojciferný rok nie je v prísnom režime povolený");
+        m.insert("Invalid Excel date", "Neplatný dátum Excelu");
+        m.insert("Invalid Unix timestamp date", "Neplatný dátum časovej pečiatky Unix");
+        m.insert("Invalid hour", "Neplatná hodina");
+        m.insert("Invalid minute", "Neplatná minúta");
+        m.insert("Invalid second", "Neplatná sekunda");
+        m.insert("Invalid time zone offset", "Neplatný posun časového pásma");
+        m.insert("Invalid week", "Neplatný týždeň");
+        m.insert("Date range ends before it starts", "Rozsah dátumov končí skôr, ako začína");
+        m.insert("on.error should be one of 'stop', 'na' or 'collect'", "on.error by malo byť 'stop', 'na' alebo 'collect'");
         m
     };
 }
@@ -249,7 +353,7 @@ pub fn tr(message: &str) -> String {
 #[macro_export]
 macro_rules! tr {
     ($msg:expr) => {
-        crate::translations::tr($msg)
+        $crate::translations::tr($msg)
     };
 }
 
@@ -272,6 +376,22 @@ pub fn day_impute_integer() -> String {
     tr("day.impute should be an integer\n")
 }
 
+pub fn day_impute_numeric_or_name() -> String {
+    tr("day.impute must be numeric or one of 'first', 'middle' or 'last'")
+}
+
+pub fn imputation_value_type() -> String {
+    tr("imputation values must be numeric, 'first', 'middle', 'last', NA or NULL")
+}
+
+pub fn hemisphere_north_or_south() -> String {
+    tr("hemisphere should be 'north' or 'south'")
+}
+
+pub fn on_error_stop_na_or_collect() -> String {
+    tr("on.error should be one of 'stop', 'na' or 'collect'")
+}
+
 
 pub fn month_not_in_range() -> String {
     tr("Month not in expected range\n")
@@ -281,8 +401,12 @@ pub fn day_not_in_range() -> String {
     tr("Day not in expected range\n")
 }
 
-pub fn format_should_be_dmy_or_mdy() -> String {
-    tr("format should be either 'dmy' or 'mdy'")
+pub fn format_should_be_dmy_mdy_or_auto() -> String {
+    tr("format should be one of 'dmy', 'mdy' or 'auto'")
+}
+
+pub fn two_digit_year_strict() -> String {
+    tr("Two digit year not allowed in strict mode")
 }
 
 pub fn unable_to_tidy_date() -> String {
@@ -331,9 +455,14 @@ mod tests {
         assert!(!missing_day_no_imputation().is_empty());
         assert!(!day_impute_integer_range().is_empty());
         assert!(!day_impute_integer().is_empty());
+        assert!(!day_impute_numeric_or_name().is_empty());
+        assert!(!imputation_value_type().is_empty());
+        assert!(!hemisphere_north_or_south().is_empty());
+        assert!(!on_error_stop_na_or_collect().is_empty());
         assert!(!month_not_in_range().is_empty());
         assert!(!day_not_in_range().is_empty());
-        assert!(!format_should_be_dmy_or_mdy().is_empty());
+        assert!(!format_should_be_dmy_mdy_or_auto().is_empty());
+        assert!(!two_digit_year_strict().is_empty());
         assert!(!unable_to_tidy_date().is_empty());
         assert!(!date_should_be_character().is_empty());
     }
@@ -346,6 +475,12 @@ mod tests {
 
         let spanish_month = SPANISH_TRANSLATIONS.get("Month not in expected range\n");
         assert_eq!(spanish_month, Some(&"Mes fuera del rango esperado\n"));
+
+        let spanish_year = SPANISH_TRANSLATIONS.get("Two digit year not allowed in strict mode");
+        assert_eq!(spanish_year, Some(&"Año de dos dígitos no permitido en modo estricto"));
+
+        let spanish_hour = SPANISH_TRANSLATIONS.get("Invalid hour");
+        assert_eq!(spanish_hour, Some(&"Hora no válida"));
     }
 
     #[test]
@@ -392,7 +527,7 @@ mod tests {
             "Month not in expected range\n",
             "date should be a character",
             "unable to tidy a date",
-            "format should be either 'dmy' or 'mdy'",
+            "format should be one of 'dmy', 'mdy' or 'auto'",
         ];
 
         let translation_maps = [
@@ -0,0 +1,98 @@
+use datefixr_core::translations::{hemisphere_north_or_south, imputation_value_type};
+use datefixr_core::{
+    DateFixError, DateFormat, FormatInference, Hemisphere, Imputation, MissingTokens, ParseOptions,
+    Trace,
+};
+use extendr_api::prelude::*;
+
+/// Named list attached to the result as the `format_inference` attribute
+pub fn inference_to_list(
+    inference: &FormatInference,
+) -> std::result::Result<List, extendr_api::Error> {
+    List::from_names_and_values(
+        ["format", "dmy_only", "mdy_only", "ambiguous", "conflicts"],
+        [
+            Robj::from(inference.format()),
+            Robj::from(inference.dmy_only as i32),
+            Robj::from(inference.mdy_only as i32),
+            Robj::from(inference.ambiguous as i32),
+            Robj::from(inference.conflicts() as i32),
+        ],
+    )
+}
+
+/// Named list ready for `as.data.frame()`, one row per stage
+pub fn trace_to_list(trace: &Trace) -> std::result::Result<List, extendr_api::Error> {
+    let steps = trace.steps();
+    let step: Vec<i32> = (1..=steps.len() as i32).collect();
+    let stage: Vec<&str> = steps.iter().map(|s| s.stage).collect();
+    let value: Vec<&str> = steps.iter().map(|s| s.value.as_str()).collect();
+    let detail: Vec<&str> = steps.iter().map(|s| s.detail.as_str()).collect();
+
+    List::from_names_and_values(
+        ["step", "stage", "value", "detail"],
+        [
+            Robj::from(step),
+            Robj::from(stage),
+            Robj::from(value),
+            Robj::from(detail),
+        ],
+    )
+}
+
+/// Read `day.impute` or `month.impute`: `NULL` fails, `NA` gives NA dates,
+/// and `"first"`, `"middle"` or `"last"` name a position in the period
+pub fn imputation_from_robj(value: &Robj) -> std::result::Result<Imputation, extendr_api::Error> {
+    if value.is_null() || value.len() == 0 {
+        return Ok(Imputation::Error);
+    }
+    if value.is_na() {
+        return Ok(Imputation::Na);
+    }
+    match value.rtype() {
+        Rtype::Integers => Ok(Imputation::Value(value.as_integer().unwrap_or_default())),
+        Rtype::Doubles => Ok(Imputation::Value(value.as_real().unwrap_or_default() as i32)),
+        Rtype::Strings => value
+            .as_str()
+            .and_then(Imputation::from_name)
+            .ok_or_else(|| imputation_value_type().into()),
+        _ => Err(imputation_value_type().into()),
+    }
+}
+
+/// Convert the `format` argument, `"dmy"`, `"mdy"` or `"auto"`
+pub fn format_from_str(value: &str) -> std::result::Result<DateFormat, extendr_api::Error> {
+    DateFormat::from_name(value).ok_or_else(|| {
+        DateFixError::InvalidFormat {
+            format: value.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Convert the `hemisphere` argument, `"north"` or `"south"`
+pub fn hemisphere_from_str(value: &str) -> std::result::Result<Hemisphere, extendr_api::Error> {
+    Hemisphere::from_name(value).ok_or_else(|| hemisphere_north_or_south().into())
+}
+
+/// Convert the `na.strings` argument, `NULL` giving the default tokens
+pub fn missing_tokens(na_strings: Option<Vec<String>>) -> MissingTokens {
+    na_strings.map_or_else(|| MissingTokens::defaults().clone(), MissingTokens::new)
+}
+
+/// Options imputing every missing component, for results which only depend
+/// on the components given
+pub fn partial_options(
+    format: &str,
+    excel: bool,
+    roman_numeral: bool,
+    hemisphere: Hemisphere,
+) -> std::result::Result<ParseOptions, extendr_api::Error> {
+    Ok(ParseOptions::new()
+        .day_impute(Imputation::First)
+        .month_impute(Imputation::First)
+        .format(format_from_str(format)?)
+        .excel(excel)
+        .roman_numeral(roman_numeral)
+        .hemisphere(hemisphere))
+}
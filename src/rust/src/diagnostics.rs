@@ -0,0 +1,102 @@
+use extendr_api::prelude::*;
+
+use datefixr_core::translations::on_error_stop_na_or_collect;
+use datefixr_core::DateFixError;
+
+/// How a column function reacts to a date it cannot tidy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// Abort the whole column on the first failure
+    Stop,
+    /// Return NA for failures and report how many there were
+    Na,
+    /// Return NA for failures and report every row
+    Collect,
+}
+
+impl ErrorMode {
+    pub fn parse(on_error: &str) -> std::result::Result<Self, extendr_api::Error> {
+        match on_error {
+            "stop" => Ok(ErrorMode::Stop),
+            "na" => Ok(ErrorMode::Na),
+            "collect" => Ok(ErrorMode::Collect),
+            _ => Err(on_error_stop_na_or_collect().into()),
+        }
+    }
+}
+
+/// Outcome of tidying one row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowStatus {
+    Ok,
+    /// Empty or missing-value input
+    Missing,
+    /// Date resolved to NA because day or month imputation is NA
+    NaImputed,
+    Error,
+}
+
+impl RowStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RowStatus::Ok => "ok",
+            RowStatus::Missing => "missing",
+            RowStatus::NaImputed => "na_imputed",
+            RowStatus::Error => "error",
+        }
+    }
+}
+
+/// Per-row record collected when `on_error` is not `"stop"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowDiagnostic {
+    pub status: RowStatus,
+    pub error: Option<DateFixError>,
+}
+
+impl RowDiagnostic {
+    pub fn new(status: RowStatus) -> Self {
+        RowDiagnostic {
+            status,
+            error: None,
+        }
+    }
+
+    pub fn failed(error: DateFixError) -> Self {
+        RowDiagnostic {
+            status: RowStatus::Error,
+            error: Some(error),
+        }
+    }
+}
+
+/// Convert row diagnostics into a named list ready for `as.data.frame()`
+pub fn diagnostics_to_list(
+    diagnostics: &[RowDiagnostic],
+    subjects: Option<&[String]>,
+) -> std::result::Result<List, extendr_api::Error> {
+    let rows: Vec<i32> = (1..=diagnostics.len() as i32).collect();
+    let subject: Vec<Option<String>> = (0..diagnostics.len())
+        .map(|i| subjects.and_then(|s| s.get(i)).cloned())
+        .collect();
+    let status: Vec<&str> = diagnostics.iter().map(|d| d.status.as_str()).collect();
+    let kind: Vec<Option<&str>> = diagnostics
+        .iter()
+        .map(|d| d.error.as_ref().map(|e| e.kind()))
+        .collect();
+    let message: Vec<Option<String>> = diagnostics
+        .iter()
+        .map(|d| d.error.as_ref().map(|e| e.message().trim_end().to_string()))
+        .collect();
+
+    List::from_names_and_values(
+        ["row", "subject", "status", "kind", "message"],
+        [
+            Robj::from(rows),
+            Robj::from(subject),
+            Robj::from(status),
+            Robj::from(kind),
+            Robj::from(message),
+        ],
+    )
+}
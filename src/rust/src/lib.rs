@@ -1,283 +1,22 @@
 #![allow(non_snake_case)]
-use chrono::{Datelike, NaiveDate};
 use extendr_api::prelude::*;
 use std::collections::HashMap;
-use std::sync::OnceLock;
 
 // Type alias for extendr's Result type for compatibility with 0.9.0
 type RResult<T> = std::result::Result<T, extendr_api::Error>;
 
-mod translations;
-use translations::*;
-mod optimizations;
-use optimizations::*;
-
-/// Month names in different languages (mirroring R months data)
-static MONTHS: OnceLock<HashMap<usize, Vec<&'static str>>> = OnceLock::new();
-static ROMAN_NUMERALS: OnceLock<Vec<&'static str>> = OnceLock::new();
-static DAYS_IN_MONTH: [u8; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
-static CURRENT_YEAR: OnceLock<i32> = OnceLock::new();
-
-/// Get cached current year to avoid repeated system calls
-#[inline]
-fn get_current_year() -> i32 {
-    *CURRENT_YEAR.get_or_init(|| chrono::Utc::now().year())
-}
-
-
-fn get_months() -> &'static HashMap<usize, Vec<&'static str>> {
-    MONTHS.get_or_init(|| {
-        let mut months = HashMap::new();
-        months.insert(
-            1,
-            vec![
-                "january",
-                "janvier",
-                "janeiro",
-                "janv",
-                "januar",
-                "jänner",
-                "jän",
-                "enero",
-                "ener",
-                "ene",
-                "jan",
-                "январь",
-                "января",
-                "янв",
-                "januari",
-            ],
-        );
-        months.insert(
-            2,
-            vec![
-                "february",
-                "février",
-                "fevrier",
-                "fevereiro",
-                "févr",
-                "fevr",
-                "fev",
-                "februar",
-                "febrero",
-                "feb",
-                "февраль",
-                "февраля",
-                "фев",
-                "februari",
-            ],
-        );
-        months.insert(
-            3,
-            vec![
-                "march", "mars", "märz", "marzo", "março", "marco", "marz", "mar", "март", "мар",
-                "maret",
-            ],
-        );
-        months.insert(
-            4,
-            vec![
-                "april",
-                "avril",
-                "abril",
-                "abr",
-                "apr",
-                "апрель",
-                "апреля",
-                "апр",
-            ],
-        );
-        months.insert(5, vec!["mayo", "may", "maio", "mai", "май", "мая", "mei"]);
-        months.insert(
-            6,
-            vec![
-                "june", "juin", "junio", "junho", "juni", "jun", "июнь", "июня", "июн",
-            ],
-        );
-        months.insert(
-            7,
-            vec![
-                "july", "juillet", "juil", "julio", "julho", "juli", "jul", "июль", "июля", "июл",
-            ],
-        );
-        months.insert(
-            8,
-            vec![
-                "august",
-                "aug",
-                "août",
-                "aout",
-                "agosto",
-                "август",
-                "авг",
-                "agustus",
-            ],
-        );
-        months.insert(
-            9,
-            vec![
-                "september",
-                "septembre",
-                "septiembre",
-                "setembro",
-                "set",
-                "sept",
-                "sep",
-                "сентябрь",
-                "сентября",
-                "сент",
-            ],
-        );
-        months.insert(
-            10,
-            vec![
-                "october",
-                "octobre",
-                "oktober",
-                "okt",
-                "octubre",
-                "outubro",
-                "oct",
-                "out",
-                "октябрь",
-                "октября",
-                "окт",
-            ],
-        );
-        months.insert(
-            11,
-            vec![
-                "november",
-                "novembre",
-                "noviembre",
-                "novembro",
-                "nov",
-                "ноябрь",
-                "ноября",
-                "ноя",
-            ],
-        );
-        months.insert(
-            12,
-            vec![
-                "december",
-                "décembre",
-                "decembre",
-                "déc",
-                "dezember",
-                "dezembro",
-                "dez",
-                "diciembre",
-                "dic",
-                "dec",
-                "декабрь",
-                "декабря",
-                "дек",
-                "desember",
-            ],
-        );
-        months
-    })
-}
-
-fn get_roman_numerals() -> &'static Vec<&'static str> {
-    ROMAN_NUMERALS.get_or_init(|| {
-        vec![
-            "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii",
-        ]
-    })
-}
-
-/// Private helper function to replace multiple patterns in a string
-
-/// Get pre-compiled ordinal regexes
-
-/// Remove ordinal suffixes from date strings (optimized with pre-compiled regexes)
-
-/// Separate date string into components
-
-/// Get pre-compiled month regexes
-
-/// Convert text month names to numeric format (optimized with pre-compiled regexes)
-
-/// Add year prefix to 2-digit years
-#[inline]
-fn year_prefix(year: &str) -> String {
-    if year.len() == 2 {
-        let current_year = get_current_year();
-        let current_two_digit = current_year % 100;
-        let year_num: i32 = year.parse().unwrap_or(0);
-
-        if year_num <= current_two_digit {
-            format!("20{}", year)
-        } else {
-            format!("19{}", year)
-        }
-    } else {
-        year.to_string()
-    }
-}
-
-/// Append year to date vector if needed
-fn append_year(mut date_vec: Vec<String>) -> Vec<String> {
-    let all_short = date_vec.iter().all(|s| s.len() <= 2 && !s.is_empty());
-
-    if all_short {
-        if date_vec.len() == 3 && date_vec[2].len() == 2 {
-            date_vec[2] = year_prefix(&date_vec[2]);
-        } else if date_vec.len() == 2 && date_vec[1].len() == 2 {
-            date_vec[1] = year_prefix(&date_vec[1]);
-        }
-    }
-    date_vec
-}
-
-/// Convert Roman numerals to Arabic numbers
-fn roman_conversion(mut date_vec: Vec<String>) -> Vec<String> {
-    if date_vec.len() >= 2 {
-        let roman_numerals = get_roman_numerals();
-        let month_str = date_vec[1].to_lowercase();
-
-        if let Some(pos) = roman_numerals.iter().position(|r| *r == month_str) {
-            let month_num = pos + 1;
-            date_vec[1] = if month_num < 10 {
-                format!("0{}", month_num)
-            } else {
-                month_num.to_string()
-            };
-        }
-    }
-    date_vec
-}
-
-/// Check if string contains only digits
-#[inline]
-fn is_numeric(s: &str) -> bool {
-    !s.chars().any(|c| !c.is_ascii_digit())
-}
-
-/// Helper function to clean numeric strings by removing trailing punctuation
-#[inline]
-fn clean_numeric_string(s: &str) -> &str {
-    s.trim_end_matches(|c: char| !c.is_ascii_digit())
-}
-
-/// Check if first element of date vector is a month name
-fn first_is_month(date_vec: &[String]) -> bool {
-    if date_vec.is_empty() {
-        return false;
-    }
-
-    let first_lower = date_vec[0].to_lowercase();
-    let months = get_months();
-
-    for month_names in months.values() {
-        if month_names.contains(&first_lower.as_str()) {
-            return true;
-        }
-    }
-    false
-}
+use datefixr_core::translations::*;
+use datefixr_core::{
+    ambiguous_candidates, parse_date_pipeline, range_interval, split_range, trace_date_pipeline,
+    DateFixError, DateFormat, FormatInference, Hemisphere, Imputation, MissingTokens, OrderEvidence,
+    ParseOptions, ParsedDate, Trace, OPEN_END,
+};
+mod convert;
+use convert::*;
+mod diagnostics;
+use diagnostics::*;
+mod parallel;
+use parallel::*;
 
 /// Validate day imputation value range
 /// @noRd
@@ -309,7 +48,11 @@ fn checkday(day_impute: Robj) -> RResult<()> {
             }
             int_val as f64
         }
-        _ => return Err("day.impute must be numeric".into()),
+        // Named policies resolve per date, e.g. "last" to the end of the month
+        Rtype::Strings if day_impute.as_str().and_then(Imputation::from_name).is_some() => {
+            return Ok(());
+        }
+        _ => return Err(day_impute_numeric_or_name().into()),
     };
 
     if val.fract() != 0.0 {
@@ -321,474 +64,810 @@ fn checkday(day_impute: Robj) -> RResult<()> {
     Ok(())
 }
 
-/// Validate and adjust date components
-fn check_output(
-    day: Option<i32>,
-    month: Option<i32>,
-    year: Option<i32>,
-) -> RResult<(Option<i32>, Option<i32>, Option<i32>)> {
-    if let Some(m) = month {
-        if m < 1 || m > 12 {
-return Err(format!("{}\n", month_not_in_range()).into());
-        }
+/// Report a date resolved to NA by NA imputation
+///
+/// Called once per row by the callers of the pipeline, so that repeated dates
+/// parsed only once still name the subject they belong to.
+fn warn_na_imputed(original_date: &str, subject: Option<&str>) {
+    // Generate the proper warning message expected by R tests
+    if let Some(subj) = subject {
+        eprintln!(
+            "WARNING: {} {} {} {} {}",
+            na_imputed_for_subject(),
+            subj,
+            date_open_paren(),
+            original_date,
+            close_paren()
+        );
+    } else {
+        eprintln!("WARNING: {} {}", na_imputed_date(), original_date);
     }
+}
 
-    let mut adjusted_day = day;
-
-    if let (Some(d), Some(m), Some(y)) = (day, month, year) {
-        if d > 31 || d < 1 {
-            // Day validation failed
-            return Err(day_not_in_range().into());
+/// Internal native function to fix a single date string efficiently
+fn fix_date_native(
+    date_str: &str,
+    options: &ParseOptions,
+    subject: Option<&str>,
+) -> Result<Option<String>, DateFixError> {
+    if options.missing().is_missing(date_str) {
+        return Ok(None);
+    }
+    match parse_date_pipeline(date_str, options) {
+        Err(DateFixError::NaImputed { .. }) => {
+            warn_na_imputed(date_str, subject);
+            Ok(None)
         }
+        result => result
+            .map(|parsed| parsed.map(|p| p.to_iso_string()))
+            .map_err(|e| e.with_input(date_str)),
+    }
+}
 
-        // Get max days for the month, accounting for leap years
-        let mut max_days = DAYS_IN_MONTH[m as usize - 1] as i32;
+/// Build the R error for a date which failed in a column, naming the row
+fn column_error(e: DateFixError, i: usize, subject: Option<&str>, date: &str) -> extendr_api::Error {
+    let subj_str = subject.unwrap_or("");
+    let id_display = if subj_str.is_empty() {
+        (i + 1).to_string()
+    } else {
+        subj_str.to_string()
+    };
+    format!(
+        "{}\n for subject {} (date: {})",
+        e.message().trim_end_matches('\n'),
+        id_display,
+        date
+    )
+    .into()
+}
 
-        if m == 2 {
-            // Check for leap year
-            if (y % 4 == 0 && y % 100 != 0) || (y % 400 == 0) {
-                max_days = 29;
-            }
+/// Classify the outcome of tidying one date of a column for diagnostics
+fn diagnose_date(
+    date: &str,
+    missing: &MissingTokens,
+    result: &Result<Option<ParsedDate>, DateFixError>,
+) -> (Option<ParsedDate>, RowDiagnostic) {
+    if missing.is_missing(date) {
+        return (None, RowDiagnostic::new(RowStatus::Missing));
+    }
+    match result {
+        Ok(Some(parsed)) => (Some(parsed.clone()), RowDiagnostic::new(RowStatus::Ok)),
+        // Only NA imputation resolves a non-missing date to NA
+        Ok(None) | Err(DateFixError::NaImputed { .. }) => {
+            (None, RowDiagnostic::new(RowStatus::NaImputed))
         }
+        Err(e) => (None, RowDiagnostic::failed(e.clone().with_input(date))),
+    }
+}
 
-        if d > max_days {
-            adjusted_day = Some(max_days);
-        }
+/// Borrow an element of an R character vector, reading `NA` as a missing date
+#[inline]
+fn column_date(date: &Rstr) -> &str {
+    if date.is_na() {
+        ""
+    } else {
+        date.as_ref()
     }
+}
 
-    Ok((adjusted_day, month, year))
+/// Build an R `Date` vector, with NA for dates which could not be resolved
+fn date_vector(parsed: &[Option<ParsedDate>]) -> RResult<Robj> {
+    let days: Vec<Option<f64>> = parsed
+        .iter()
+        .map(|p| p.as_ref().and_then(|p| p.days_since_epoch()))
+        .collect();
+    let mut dates: Robj = days.into();
+    dates.set_class(["Date"])?;
+    Ok(dates)
 }
 
-/// Combine partial date components into a formatted date string
-fn combine_partial_date(
-    day: Option<i32>,
-    month: Option<i32>,
-    year: Option<i32>,
-    original_date: &str,
-    subject: Option<&str>,
-) -> Option<String> {
-    match (day, month, year) {
-        (Some(d), Some(m), Some(y)) => Some(format!("{:04}-{:02}-{:02}", y, m, d)),
-        _ => {
-            // Generate the proper warning message expected by R tests
-            if let Some(subj) = subject {
-                eprintln!(
-                    "WARNING: {} {} {} {} {}",
-                    na_imputed_for_subject(),
-                    subj,
-                    date_open_paren(),
-                    original_date,
-                    close_paren()
-                );
-            } else {
-                eprintln!("WARNING: {} {}", na_imputed_date(), original_date);
-            }
-            None
-        }
-    }
+/// Convert parsed dates to an R `POSIXct` vector in UTC
+///
+/// Times without an offset are read as UTC and flagged in a `wall_clock`
+/// attribute, so they can be moved to the requested time zone in R.
+fn datetime_vector(parsed: &[Option<ParsedDate>]) -> RResult<Robj> {
+    let seconds: Vec<Option<f64>> = parsed
+        .iter()
+        .map(|p| p.as_ref().and_then(|p| p.seconds_since_epoch()))
+        .collect();
+    let wall_clock: Vec<bool> = parsed
+        .iter()
+        .map(|p| matches!(p, Some(p) if p.time.and_then(|t| t.offset).is_none()))
+        .collect();
+    let mut datetimes: Robj = seconds.into();
+    datetimes.set_class(["POSIXct", "POSIXt"])?;
+    datetimes.set_attrib("tzone", "UTC")?;
+    datetimes.set_attrib("wall_clock", wall_clock)?;
+    Ok(datetimes)
 }
 
-/// Handle 4-digit year only dates (e.g., "2020")
-fn handle_year_only_date(
-    cleaned_date: &str,
-    day_impute: Option<i32>,
-    month_impute: Option<i32>,
-    day_impute_na: bool,
-    month_impute_na: bool,
-) -> RResult<Option<String>> {
-    if cleaned_date.len() == 4 && is_numeric(cleaned_date) {
-        let year = cleaned_date.parse::<i32>().unwrap();
-        return if month_impute_na || day_impute_na {
-            // Either month.impute or day.impute is NA, should return None and let R generate warning
-            Ok(None)
-        } else if month_impute.is_none() {
-            Err(missing_month_no_imputation().into())
-        } else if day_impute.is_none() {
-            Err(missing_day_no_imputation().into())
-        } else if let (Some(m), Some(d)) = (month_impute, day_impute) {
-            // Only format if we have valid non-NA values
-            if m != -1 && d != -1 {
-                Ok(Some(format!("{:04}-{:02}-{:02}", year, m, d)))
-            } else {
-                Ok(None)
-            }
-        } else {
-            Ok(None)
-        };
-    }
-    // Not a year-only date, continue with other parsing
-    Ok(None)
+/// Distinct values of a column, and for every row the index of its value
+///
+/// Parsing options are fixed for a call, so each distinct string only needs
+/// to go through the pipeline once.
+fn distinct_values<'a>(dates: &[&'a str]) -> (Vec<&'a str>, Vec<usize>) {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut distinct = Vec::new();
+    let rows = dates
+        .iter()
+        .map(|&date| {
+            *index.entry(date).or_insert_with(|| {
+                distinct.push(date);
+                distinct.len() - 1
+            })
+        })
+        .collect();
+    (distinct, rows)
 }
 
-/// Handle pure numeric dates (Excel serial dates or Unix timestamps)
-fn handle_numeric_dates(
-    cleaned_date: &str,
-    excel: bool,
-) -> RResult<Option<String>> {
-    // Exclude 4-digit numbers as they're more likely to be years than timestamps
-    if is_numeric(cleaned_date) && cleaned_date.len() != 4 {
-        if let Ok(num_date) = cleaned_date.parse::<i64>() {
-            return if excel {
-                // Excel dates - account for Excel's 1900 leap year bug
-                let adjusted_date = num_date;
-                if let Some(excel_date) = NaiveDate::from_ymd_opt(1899, 12, 30) {
-                    if let Some(result_date) =
-                        excel_date.checked_add_signed(chrono::Duration::days(adjusted_date))
-                    {
-                        Ok(Some(result_date.format("%Y-%m-%d").to_string()))
-                    } else {
-                        Err("Invalid Excel date".into())
-                    }
-                } else {
-                    Err("Invalid Excel base date".into())
-                }
-            } else {
-                // Unix timestamp
-                if let Some(unix_date) = NaiveDate::from_ymd_opt(1970, 1, 1) {
-                    if let Some(result_date) =
-                        unix_date.checked_add_signed(chrono::Duration::days(num_date))
-                    {
-                        Ok(Some(result_date.format("%Y-%m-%d").to_string()))
-                    } else {
-                        Err("Invalid Unix timestamp date".into())
-                    }
-                } else {
-                    Err("Invalid Unix base date".into())
-                }
-            };
+/// Parse the distinct dates of a column, missing ones resolving to `Ok(None)`
+///
+/// With `format = "auto"` every date is read both as DMY and as MDY. Dates
+/// valid in only one order keep that reading, and ambiguous dates use the
+/// order with more evidence across the column, weighted by `rows`.
+fn parse_distinct(
+    distinct: &[&str],
+    rows: &[usize],
+    options: &ParseOptions,
+    threads: usize,
+) -> (Vec<Result<Option<ParsedDate>, DateFixError>>, Option<FormatInference>) {
+    let missing = options.missing();
+    let parse = |date: &str, options: &ParseOptions| {
+        if missing.is_missing(date) {
+            return Ok(None);
         }
+        parse_date_pipeline(date, options)
+    };
+    if options.format != DateFormat::Auto {
+        let results = map_column(distinct.len(), threads, |i| parse(distinct[i], options));
+        return (results, None);
     }
-    // Not a pure numeric date, continue with other parsing
-    Ok(None)
-}
 
+    let dmy = options.clone().format(DateFormat::Dmy);
+    let mdy = options.clone().format(DateFormat::Mdy);
+    let candidates = map_column(distinct.len(), threads, |i| {
+        (parse(distinct[i], &dmy), parse(distinct[i], &mdy))
+    });
+    let evidence: Vec<OrderEvidence> = candidates
+        .iter()
+        .map(|(dmy, mdy)| OrderEvidence::from_results(dmy, mdy))
+        .collect();
+    let mut inference = FormatInference::default();
+    for &row in rows {
+        inference.add(evidence[row], 1);
+    }
 
-/// Process a single date with error handling for batch operations
-fn process_single_date_with_error_handling(
-    date: &str,
-    day_impute: Option<i32>,
-    month_impute: Option<i32>,
-    subject: Option<&str>,
+    let results = candidates
+        .into_iter()
+        .zip(evidence)
+        .map(|((dmy, mdy), evidence)| match evidence {
+            OrderEvidence::DmyOnly => dmy,
+            OrderEvidence::MdyOnly => mdy,
+            _ if inference.format() == "mdy" => mdy,
+            _ => dmy,
+        })
+        .collect();
+    (results, Some(inference))
+}
+
+/// Analyze and fix date strings in a whole column of a DataFrame
+///
+/// Returns an R `Date` vector. With `on_error = "stop"` the first failure
+/// aborts the column. Otherwise failed dates become NA, and either the
+/// number of failures (`"na"`) or a per-row `diagnostics` attribute
+/// (`"collect"`) is attached. Each distinct date is parsed once,
+/// and when there are more than `PARALLEL_THRESHOLD` of them they are split
+/// across `threads` threads. With `format = "auto"` the day/month order is
+/// inferred from the column and reported in a `format_inference` attribute.
+/// With `datetime` a `POSIXct` vector including the time of day is returned.
+/// @noRd
+#[extendr]
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+fn fix_date_column(
+    dates: Strings,
+    day_impute: Robj,
+    month_impute: Robj,
+    subjects: Option<Vec<String>>,
     format: &str,
     excel: bool,
     roman_numeral: bool,
-) -> RResult<Option<String>> {
-    // Propagate all errors: Ok(None) for genuinely-absent dates is handled
-    // inside process_date_pipeline (empty/NA inputs, NA imputation), not here.
-    fix_date_native(date, day_impute, month_impute, subject, format, excel, roman_numeral)
-}
-
-/// Parse date components from date vector based on length and format
-fn parse_date_components(
-    date_vec: &[String],
-    effective_format: &str,
-    day_impute: Option<i32>,
-    day_impute_na: bool,
-) -> RResult<(Option<i32>, Option<i32>, Option<i32>)> {
-    if date_vec.len() < 3 {
-        // Handle MM/YYYY or YYYY/MM format
-        if day_impute.is_none() {
-            // When day_impute is None (NULL), we need to throw an error
-            return Err(missing_day_no_imputation().into());
-        } else if day_impute_na {
-            // When day_impute is Some(-1) (NA), we return None and let R handle the warning
-            return Err("NA imputation requested".into()); // Special error to signal NA return
-        }
+    on_error: &str,
+    na_strings: Option<Vec<String>>,
+    threads: i32,
+    strict: bool,
+    datetime: bool,
+    hemisphere: &str,
+) -> RResult<Robj> {
+    let options = ParseOptions::new()
+        .day_impute(imputation_from_robj(&day_impute)?)
+        .month_impute(imputation_from_robj(&month_impute)?)
+        .format(format_from_str(format)?)
+        .excel(excel)
+        .roman_numeral(roman_numeral)
+        .strict(strict)
+        .hemisphere(hemisphere_from_str(hemisphere)?)
+        .missing_tokens(missing_tokens(na_strings));
+    let threads = threads.max(1) as usize;
+
+    // Borrow the strings up front: R objects must not be touched off the main thread
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+    let subject_at = |i: usize| subjects.as_ref().and_then(|s| s.get(i)).map(|s| s.as_str());
+    let (distinct, rows) = distinct_values(&dates);
+
+    let mode = ErrorMode::parse(on_error)?;
+    let (results, inference) = parse_distinct(&distinct, &rows, &options, threads);
+    let to_vector = if datetime {
+        datetime_vector
+    } else {
+        date_vector
+    };
 
-        let day = day_impute.unwrap();
-
-        if date_vec.len() == 2 {
-            if date_vec[0].len() == 4 {
-                // YYYY/MM
-                validate_year_length(&date_vec[0])?;
-                let year = date_vec[0].parse::<i32>().map_err(|_| "Invalid year")?;
-                let month = date_vec[1].parse::<i32>().map_err(|_| "Invalid month")?;
-                Ok((Some(day), Some(month), Some(year)))
-            } else if date_vec[1].len() == 4 {
-                // MM/YYYY
-                validate_year_length(&date_vec[1])?;
-                let month = date_vec[0].parse::<i32>().map_err(|_| "Invalid month")?;
-                let year = date_vec[1].parse::<i32>().map_err(|_| "Invalid year")?;
-                Ok((Some(day), Some(month), Some(year)))
-            } else {
-                Err("Unable to determine date format".into())
+    let mut result = if mode == ErrorMode::Stop {
+        // Replay outcomes row by row so warnings name the right subject and
+        // the first failing row is reported, whichever thread found it
+        let mut parsed = Vec::with_capacity(rows.len());
+        for (i, &row) in rows.iter().enumerate() {
+            match &results[row] {
+                Ok(date) => parsed.push(date.clone()),
+                Err(DateFixError::NaImputed { .. }) => {
+                    warn_na_imputed(dates[i], subject_at(i));
+                    parsed.push(None);
+                }
+                Err(e) => return Err(column_error(e.clone(), i, subject_at(i), dates[i])),
             }
-        } else {
-            Err("Insufficient date components".into())
         }
+        to_vector(&parsed)?
     } else {
-        // Handle full date with day, month, year
-        if date_vec[0].len() == 4 {
-            // YYYY/MM/DD
-            validate_year_length(&date_vec[0])?;
-            let year = date_vec[0].parse::<i32>().map_err(|_| "Invalid year")?;
-            let month = date_vec[1].parse::<i32>().map_err(|_| "Invalid month")?;
-            let day = date_vec[2].parse::<i32>().map_err(|_| "Invalid day")?;
-            Ok((Some(day), Some(month), Some(year)))
-        } else {
-            match effective_format {
-                "dmy" => {
-                    // DD/MM/YYYY
-                    let day = date_vec[0].parse::<i32>().map_err(|_| "Invalid day")?;
-                    let month = date_vec[1].parse::<i32>().map_err(|_| "Invalid month")?;
-                    validate_year_length(&date_vec[2])?;
-                    let year = date_vec[2].parse::<i32>().map_err(|_| "Invalid year")?;
-                    Ok((Some(day), Some(month), Some(year)))
-                }
-                "mdy" => {
-                    // MM/DD/YYYY
-                    let month = date_vec[0].parse::<i32>().map_err(|_| "Invalid month")?;
-                    let day_str = clean_numeric_string(&date_vec[1]);
-                    let day = day_str.parse::<i32>().map_err(|_| "Invalid day")?;
-                    validate_year_length(&date_vec[2])?;
-                    let year = date_vec[2].parse::<i32>().map_err(|_| "Invalid year")?;
-                    Ok((Some(day), Some(month), Some(year)))
-                }
-                _ => Err(format_should_be_dmy_or_mdy().into()),
+        // Keep going past failures, recording every row only when collecting
+        let mut parsed = Vec::with_capacity(rows.len());
+        let mut diagnostics = Vec::new();
+        let mut failed = 0;
+        for (i, &row) in rows.iter().enumerate() {
+            let (date, diagnostic) = diagnose_date(dates[i], options.missing(), &results[row]);
+            match diagnostic.status {
+                RowStatus::NaImputed => warn_na_imputed(dates[i], subject_at(i)),
+                RowStatus::Error => failed += 1,
+                _ => {}
+            }
+            parsed.push(date);
+            if mode == ErrorMode::Collect {
+                diagnostics.push(diagnostic);
             }
         }
-    }
-}
+        let mut result = to_vector(&parsed)?;
+        if mode == ErrorMode::Collect {
+            result.set_attrib(
+                "diagnostics",
+                diagnostics_to_list(&diagnostics, subjects.as_deref())?,
+            )?;
+        } else {
+            result.set_attrib("failed", failed)?;
+        }
+        result
+    };
 
-/// Helper function to validate year string length
-#[inline]
-fn validate_year_length(year_str: &str) -> RResult<()> {
-    if year_str.len() > 4 {
-        Err(unable_to_tidy_date().into())
-    } else {
-        Ok(())
+    if let Some(inference) = inference {
+        result.set_attrib("format_inference", inference_to_list(&inference)?)?;
     }
+    Ok(result)
 }
 
-/// Common date processing pipeline used by both fix_date and fix_date_native
-fn process_date_pipeline(
-    date_str: &str,
-    day_impute: Option<i32>,
-    month_impute: Option<i32>, 
-    subject: Option<&str>,
+/// Parse a column of dates and report how each one was resolved
+/// @noRd
+#[extendr]
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+fn fix_date_details_column(
+    dates: Strings,
+    day_impute: Robj,
+    month_impute: Robj,
     format: &str,
     excel: bool,
     roman_numeral: bool,
-) -> RResult<Option<String>> {
-    // Convert -1 sentinel values to special marker for NA (for direct calls from R)
-    // Keep -1 to distinguish between NA (-1) and NULL (None)
-    let day_impute_na = day_impute == Some(-1);
-    let month_impute_na = month_impute == Some(-1);
-    
-    // Handle null/NA/empty dates and common missing-value sentinels
-    if date_str.is_empty() || date_str == "NA" || date_str == "?" {
-        return Ok(None);
-    }
+    na_strings: Option<Vec<String>>,
+    strict: bool,
+    hemisphere: &str,
+    edtf: bool,
+) -> RResult<List> {
+    let options = ParseOptions::new()
+        .day_impute(imputation_from_robj(&day_impute)?)
+        .month_impute(imputation_from_robj(&month_impute)?)
+        .format(format_from_str(format)?)
+        .excel(excel)
+        .roman_numeral(roman_numeral)
+        .strict(strict)
+        .hemisphere(hemisphere_from_str(hemisphere)?)
+        .edtf(edtf)
+        .missing_tokens(missing_tokens(na_strings));
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+    let (distinct, rows) = distinct_values(&dates);
+    let (results, _) = parse_distinct(&distinct, &rows, &options, 1);
+    let parsed = rows
+        .iter()
+        .enumerate()
+        .map(|(i, &row)| match &results[row] {
+            Ok(date) => Ok(date.clone()),
+            Err(DateFixError::NaImputed { .. }) => Ok(None),
+            Err(e) => Err(column_error(e.clone(), i, None, dates[i])),
+        })
+        .collect::<RResult<Vec<Option<ParsedDate>>>>()?;
 
-    // Try fast-path parsing for common formats first
-    if let Some((day, month, year)) = fast_path_parse_date(date_str, format) {
-        // Still need to validate and adjust the date components (e.g., Feb 30 -> Feb 28)
-        let (adjusted_day, adjusted_month, adjusted_year) = check_output(
-            Some(day as i32),
-            Some(month as i32),
-            Some(year as i32),
-        )?;
-        
-        return Ok(combine_partial_date(
-            adjusted_day,
-            adjusted_month,
-            adjusted_year,
-            date_str,
-            subject,
-        ));
-    }
+    let field = |f: fn(&ParsedDate) -> i32| -> Vec<Option<i32>> {
+        parsed.iter().map(|p| p.as_ref().map(f)).collect()
+    };
+    let flag = |f: fn(&ParsedDate) -> bool| -> Vec<Option<bool>> {
+        parsed.iter().map(|p| p.as_ref().map(f)).collect()
+    };
+    let label = |f: fn(&ParsedDate) -> Option<&'static str>| -> Vec<Option<&'static str>> {
+        parsed.iter().map(|p| p.as_ref().and_then(f)).collect()
+    };
 
-    // Clean the date string using combined approach
-    let cleaned_date = clean_date_string_combined(date_str).into_owned();
-
-    // Try handling as year-only date
-    match handle_year_only_date(&cleaned_date, day_impute, month_impute, day_impute_na, month_impute_na) {
-        Ok(Some(result)) => return Ok(Some(result)),
-        Ok(None) => {
-            // Year-only date was handled but resulted in None (NA imputation case)
-            // Check if this was actually a year-only date that should return None
-            if cleaned_date.len() == 4 && is_numeric(&cleaned_date) {
-                return Ok(None);
-            }
-            // Otherwise, continue with normal processing
-        }
-        Err(_) => {
-            // Not a year-only date or error occurred, continue with normal processing
-        }
-    }
+    List::from_names_and_values(
+        [
+            "date",
+            "year",
+            "month",
+            "day",
+            "day_imputed",
+            "month_imputed",
+            "day_clamped",
+            "format",
+            "language",
+            "source",
+            "approximate",
+            "qualifier",
+            "uncertain",
+        ],
+        [
+            date_vector(&parsed)?,
+            Robj::from(field(|p| p.year)),
+            Robj::from(field(|p| p.month)),
+            Robj::from(field(|p| p.day)),
+            Robj::from(flag(|p| p.day_imputed)),
+            Robj::from(flag(|p| p.month_imputed)),
+            Robj::from(flag(|p| p.day_clamped)),
+            Robj::from(label(|p| p.format)),
+            Robj::from(label(|p| p.language)),
+            Robj::from(label(|p| Some(p.source.as_str()))),
+            Robj::from(flag(|p| p.approximate())),
+            Robj::from(label(|p| p.qualifier.map(|q| q.as_str()))),
+            Robj::from(flag(|p| p.uncertain)),
+        ],
+    )
+}
 
-    // Try handling as pure numeric date (Excel/Unix)
-    if let Ok(Some(result)) = handle_numeric_dates(&cleaned_date, excel) {
-        return Ok(Some(result));
+/// Parse a column of dates for CDISC datasets
+///
+/// Returns the date at the precision of the input as an ISO 8601 string
+/// (`dtc`), the imputed date (`dt`) and its imputation flag (`dtf`). Dates
+/// missing a component which is imputed as NA or not at all keep their
+/// partial `dtc`, with `dt` and `dtf` left NA.
+/// @noRd
+#[extendr]
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+fn fix_date_cdisc_column(
+    dates: Strings,
+    day_impute: Robj,
+    month_impute: Robj,
+    format: &str,
+    excel: bool,
+    roman_numeral: bool,
+    na_strings: Option<Vec<String>>,
+    hemisphere: &str,
+) -> RResult<List> {
+    let day_impute = imputation_from_robj(&day_impute)?;
+    let month_impute = imputation_from_robj(&month_impute)?;
+    // Partial dates are valid in SDTM, so they are always read; whether they
+    // get an imputed date is decided afterwards
+    let or_first = |impute: Imputation| {
+        if impute.imputes() {
+            impute
+        } else {
+            Imputation::First
+        }
+    };
+    let options = ParseOptions::new()
+        .day_impute(or_first(day_impute))
+        .month_impute(or_first(month_impute))
+        .format(format_from_str(format)?)
+        .excel(excel)
+        .roman_numeral(roman_numeral)
+        .hemisphere(hemisphere_from_str(hemisphere)?)
+        .missing_tokens(missing_tokens(na_strings));
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+    let (distinct, rows) = distinct_values(&dates);
+    let (results, _) = parse_distinct(&distinct, &rows, &options, 1);
+
+    let mut dtc = Vec::with_capacity(rows.len());
+    let mut dt = Vec::with_capacity(rows.len());
+    let mut dtf = Vec::with_capacity(rows.len());
+    for (i, &row) in rows.iter().enumerate() {
+        let parsed = match &results[row] {
+            Ok(parsed) => parsed.clone(),
+            Err(e) => return Err(column_error(e.clone(), i, None, dates[i])),
+        };
+        dtc.push(parsed.as_ref().map(|p| p.to_partial_iso_string()));
+        let imputed = parsed.filter(|p| {
+            (!p.day_imputed || day_impute.imputes())
+                && (!p.month_imputed || month_impute.imputes())
+        });
+        dtf.push(imputed.as_ref().and_then(|p| p.imputation_flag()));
+        dt.push(imputed);
     }
 
-    // Process the date string - convert to Vec<String> from Vec<&str>
-    let date_vec_str = separate_date_optimized(&cleaned_date);
-    let mut date_vec: Vec<String> = date_vec_str.into_iter().map(|s| s.to_string()).collect();
-    // Debug information removed for production
-
-    // Check if first element is a month name (forces MDY format)
-    let effective_format = if first_is_month(&date_vec) {
-        "mdy"
-    } else {
-        format
-    };
+    List::from_names_and_values(
+        ["dtc", "dt", "dtf"],
+        [Robj::from(dtc), date_vector(&dt)?, Robj::from(dtf)],
+    )
+}
 
-    // Convert text months to numbers in date components
-    for component in &mut date_vec {
-        let converted = convert_text_month_optimized(component).into_owned();
-        *component = converted;
-    }
+/// Parse a column with every missing component imputed, for results which
+/// only depend on the components given
+///
+/// Only dates which cannot be tidied at all raise an error. Missing dates
+/// give `None`.
+fn parse_partial_column(
+    dates: Strings,
+    format: &str,
+    excel: bool,
+    roman_numeral: bool,
+    na_strings: Option<Vec<String>>,
+    edtf: bool,
+) -> RResult<Vec<Option<ParsedDate>>> {
+    let options = partial_options(format, excel, roman_numeral, Hemisphere::default())?
+        .edtf(edtf)
+        .missing_tokens(missing_tokens(na_strings));
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+    let (distinct, rows) = distinct_values(&dates);
+    let (results, _) = parse_distinct(&distinct, &rows, &options, 1);
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, &row)| match &results[row] {
+            Ok(parsed) => Ok(parsed.clone()),
+            Err(e) => Err(column_error(e.clone(), i, None, dates[i])),
+        })
+        .collect()
+}
 
-    // Handle Roman numerals
-    if roman_numeral {
-        date_vec = roman_conversion(date_vec);
-    }
+/// Precision of each date in a column: `"century"`, `"decade"`, `"year"`,
+/// `"season"`, `"quarter"`, `"month"`, `"week"` or `"day"`
+///
+/// Every missing component is imputed, so only dates which cannot be tidied
+/// at all raise an error. Missing dates give NA.
+/// @noRd
+#[extendr]
+#[no_mangle]
+fn date_precision_column(
+    dates: Strings,
+    format: &str,
+    excel: bool,
+    roman_numeral: bool,
+    na_strings: Option<Vec<String>>,
+    edtf: bool,
+) -> RResult<Vec<Option<&'static str>>> {
+    let parsed = parse_partial_column(dates, format, excel, roman_numeral, na_strings, edtf)?;
+    Ok(parsed
+        .iter()
+        .map(|p| p.as_ref().map(|p| p.precision().as_str()))
+        .collect())
+}
 
-    // Check for overly long components before processing
-    if date_str.len() > 200 || date_vec.iter().any(|s| s.len() > 6) {
-        return Err(unable_to_tidy_date().into());
-    }
+/// A date of a column, or the two sides of a date range
+///
+/// Blank sides and the open and unknown ends of EDTF intervals are `None`.
+enum ColumnRange {
+    Date(Option<ParsedDate>),
+    Range(Option<ParsedDate>, Option<ParsedDate>),
+}
 
-    // Check for components that are too long to be valid date parts
-    // Any numeric component longer than 4 digits is invalid for years, and anything over 2 digits is invalid for days/months
-    for component in &date_vec {
-        if is_numeric(component) && component.len() > 4 {
-            return Err(unable_to_tidy_date().into());
+/// Parse a column which may hold date ranges, giving each row its
+/// `ColumnRange` and the two sides it was split into, if any
+///
+/// Both sides of a range are parsed as dates of their own, so they count
+/// towards the format inferred for the column.
+#[allow(clippy::type_complexity)]
+fn parse_range_column(
+    dates: &[&str],
+    options: &ParseOptions,
+) -> RResult<Vec<(ColumnRange, Option<(String, String)>)>> {
+    let (distinct, rows) = distinct_values(dates);
+    let ranges: Vec<Option<(String, String)>> = distinct.iter().map(|d| split_range(d, options)).collect();
+    let mut sides: Vec<&str> = Vec::new();
+    let mut first_side = Vec::with_capacity(distinct.len());
+    for (&date, range) in distinct.iter().zip(&ranges) {
+        first_side.push(sides.len());
+        match range {
+            // An open end is parsed as a blank date
+            Some((start, end)) => sides.extend([start.as_str(), end.as_str()].map(|side| {
+                if side == OPEN_END {
+                    ""
+                } else {
+                    side
+                }
+            })),
+            None => sides.push(date),
         }
     }
+    let side_rows: Vec<usize> = rows
+        .iter()
+        .flat_map(|&row| {
+            let count = if ranges[row].is_some() { 2 } else { 1 };
+            first_side[row]..first_side[row] + count
+        })
+        .collect();
+    let (results, _) = parse_distinct(&sides, &side_rows, options, 1);
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, &row)| {
+            let side = first_side[row];
+            let parsed = match &ranges[row] {
+                None => results[side].clone().map(ColumnRange::Date),
+                Some(_) => results[side].clone().and_then(|start| {
+                    let end = results[side + 1].clone()?;
+                    if let (Some(start), Some(end)) = (&start, &end) {
+                        range_interval(start, end)?;
+                    }
+                    Ok(ColumnRange::Range(start, end))
+                }),
+            };
+            parsed
+                .map(|parsed| (parsed, ranges[row].clone()))
+                .map_err(|e| column_error(e, i, None, dates[i]))
+        })
+        .collect()
+}
 
-    // Reject dates with more than 3 components (more than 2 separators)
-    if date_vec.len() > 3 {
-        return Err(unable_to_tidy_date().into());
+/// First and last day each date of a column could refer to
+///
+/// A year spans the whole year and a month without a day the whole month.
+/// Ranges such as "March-April 2020" run from the start of their first side
+/// to the end of their second. Dates given with a qualifier such as "circa"
+/// are flagged in `approximate`. Missing dates, and with `edtf` the open or
+/// unknown ends of EDTF intervals such as "1985/..", give NA bounds.
+/// @noRd
+#[extendr]
+#[no_mangle]
+fn date_interval_column(
+    dates: Strings,
+    format: &str,
+    excel: bool,
+    roman_numeral: bool,
+    na_strings: Option<Vec<String>>,
+    hemisphere: &str,
+    edtf: bool,
+) -> RResult<List> {
+    let hemisphere = hemisphere_from_str(hemisphere)?;
+    let options = partial_options(format, excel, roman_numeral, hemisphere)?
+        .edtf(edtf)
+        .missing_tokens(missing_tokens(na_strings));
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+
+    let mut lower = Vec::with_capacity(dates.len());
+    let mut upper = Vec::with_capacity(dates.len());
+    let mut approximate = Vec::with_capacity(dates.len());
+    for (parsed, _) in parse_range_column(&dates, &options)? {
+        let (start, end) = match parsed {
+            ColumnRange::Date(date) => (date.clone(), date),
+            ColumnRange::Range(start, end) => (start, end),
+        };
+        let l = start.map(|start| start.interval().0);
+        let u = end.map(|end| end.interval().1);
+        approximate.push(match (&l, &u) {
+            (None, None) => None,
+            _ => Some(l.iter().chain(&u).any(|p| p.approximate())),
+        });
+        lower.push(l);
+        upper.push(u);
     }
 
-    // Append year prefixes if needed
-    date_vec = append_year(date_vec);
+    List::from_names_and_values(
+        ["lower", "upper", "approximate"],
+        [
+            date_vector(&lower)?,
+            date_vector(&upper)?,
+            Robj::from(approximate),
+        ],
+    )
+}
 
-    // Parse the date components based on length and format
-    let (day, month, year) = match parse_date_components(&date_vec, &effective_format, day_impute, day_impute_na) {
-        Ok(components) => components,
-        Err(e) => {
-            if e.to_string().contains("NA imputation requested") {
-                return Ok(None);
+/// Write each date of a column in the Extended Date/Time Format (EDTF)
+///
+/// Dates keep the precision they were given at, and ranges become EDTF
+/// intervals whose open and unknown ends are kept. Missing dates give NA.
+/// @noRd
+#[extendr]
+#[no_mangle]
+fn fix_date_edtf_column(
+    dates: Strings,
+    format: &str,
+    excel: bool,
+    roman_numeral: bool,
+    na_strings: Option<Vec<String>>,
+    hemisphere: &str,
+) -> RResult<Robj> {
+    let hemisphere = hemisphere_from_str(hemisphere)?;
+    let options = partial_options(format, excel, roman_numeral, hemisphere)?
+        .edtf(true)
+        .missing_tokens(missing_tokens(na_strings));
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+
+    // A side which is itself an interval, such as a season, contributes its
+    // start to the start of the range and its end to the end
+    let side = |date: Option<ParsedDate>, given: &str, end: bool| match date {
+        Some(date) => {
+            let edtf = date.to_edtf_string();
+            match edtf.split_once('/') {
+                Some((start, _)) if !end => start.to_string(),
+                Some((_, last)) => last.to_string(),
+                None => edtf,
             }
-            return Err(e);
         }
+        None if given == OPEN_END => OPEN_END.to_string(),
+        None => String::new(),
     };
-
-    // Validate and adjust the date components
-    let (adjusted_day, adjusted_month, adjusted_year) = check_output(day, month, year)?;
-
-    // Combine into final date string
-    Ok(combine_partial_date(
-        adjusted_day,
-        adjusted_month,
-        adjusted_year,
-        date_str,
-        subject,
-    ))
+    let edtf: Vec<Option<String>> = parse_range_column(&dates, &options)?
+        .into_iter()
+        .map(|(parsed, sides)| match (parsed, sides) {
+            (ColumnRange::Date(date), _) => date.map(|date| date.to_edtf_string()),
+            (ColumnRange::Range(start, end), Some((given_start, given_end))) => Some(format!(
+                "{}/{}",
+                side(start, &given_start, false),
+                side(end, &given_end, true)
+            )),
+            (ColumnRange::Range(..), None) => None,
+        })
+        .collect();
+    Ok(Robj::from(edtf))
 }
 
-/// Internal native function to fix a single date string efficiently
-fn fix_date_native(
-    date_str: &str,
-    day_impute: Option<i32>,
-    month_impute: Option<i32>,
-    subject: Option<&str>,
-    format: &str,
+/// List the rows of a column which tidy to different dates as DMY and as MDY
+/// @noRd
+#[extendr]
+#[no_mangle]
+fn ambiguous_dates_column(
+    dates: Strings,
     excel: bool,
     roman_numeral: bool,
-) -> RResult<Option<String>> {
-    process_date_pipeline(date_str, day_impute, month_impute, subject, format, excel, roman_numeral)
+    na_strings: Option<Vec<String>>,
+) -> RResult<List> {
+    let missing = missing_tokens(na_strings);
+    let dates: Vec<&str> = dates.iter().map(column_date).collect();
+    let (distinct, rows) = distinct_values(&dates);
+    let candidates: Vec<Option<(ParsedDate, ParsedDate)>> = distinct
+        .iter()
+        .map(|&date| {
+            if missing.is_missing(date) {
+                return None;
+            }
+            ambiguous_candidates(date, excel, roman_numeral)
+        })
+        .collect();
+
+    let mut row = Vec::new();
+    let mut dmy = Vec::new();
+    let mut mdy = Vec::new();
+    for (i, &index) in rows.iter().enumerate() {
+        if let Some((d, m)) = &candidates[index] {
+            row.push(i as i32 + 1);
+            dmy.push(Some(d.clone()));
+            mdy.push(Some(m.clone()));
+        }
+    }
+
+    List::from_names_and_values(
+        ["row", "dmy", "mdy"],
+        [Robj::from(row), date_vector(&dmy)?, date_vector(&mdy)?],
+    )
 }
 
-/// Analyze and fix date strings in a whole column of a DataFrame
+/// Trace every stage of the parsing pipeline for a single date
 /// @noRd
 #[extendr]
 #[no_mangle]
-fn fix_date_column(
-    dates: Vec<String>,
-    day_impute: i32,
-    month_impute: i32,
-    subjects: Option<Vec<String>>,
+#[allow(clippy::too_many_arguments)]
+fn explain_date_steps(
+    date: Strings,
+    day_impute: Robj,
+    month_impute: Robj,
     format: &str,
     excel: bool,
     roman_numeral: bool,
-) -> RResult<Vec<Option<String>>> {
-    // Process all dates using native Rust function to avoid R object conversions
-    let day_impute_opt = Some(day_impute);
-    let month_impute_opt = Some(month_impute);
-
-    dates.into_iter().enumerate().map(|(i, date)| {
-        let subject = subjects.as_ref().and_then(|s| s.get(i));
-
-        // Use helper function for cleaner error handling
-        process_single_date_with_error_handling(
-            &date,
-            day_impute_opt,
-            month_impute_opt,
-            subject.map(|s| s.as_str()),
-            format,
-            excel,
-            roman_numeral,
-        )
-        .map_err(|e| {
-            let subj_str = subject.map(|s| s.as_str()).unwrap_or("");
-            let id_display = if subj_str.is_empty() {
-                (i + 1).to_string()
-            } else {
-                subj_str.to_string()
-            };
-            format!(
-                "{}\n for subject {} (date: {})",
-                e.to_string().trim_end_matches('\n'),
-                id_display,
-                date
+    na_strings: Option<Vec<String>>,
+    strict: bool,
+    hemisphere: &str,
+    edtf: bool,
+) -> RResult<List> {
+    let options = ParseOptions::new()
+        .day_impute(imputation_from_robj(&day_impute)?)
+        .month_impute(imputation_from_robj(&month_impute)?)
+        .format(format_from_str(format)?)
+        .excel(excel)
+        .roman_numeral(roman_numeral)
+        .strict(strict)
+        .hemisphere(hemisphere_from_str(hemisphere)?)
+        .edtf(edtf)
+        .missing_tokens(missing_tokens(na_strings));
+    let date = date.iter().next().map_or("", column_date);
+
+    let mut trace = Trace::enabled();
+    trace.record("input", || (date.to_string(), String::new()));
+    if options.missing().is_missing(date) {
+        trace.record("missing", || {
+            (String::new(), "missing value, returned as NA".into())
+        });
+        return trace_to_list(&trace);
+    }
+
+    let result = trace_date_pipeline(date, &options, &mut trace);
+    match result {
+        Ok(Some(parsed)) => trace.record("result", || {
+            let format = parsed.format.unwrap_or("serial");
+            (
+                parsed.to_iso_string(),
+                format!("read as {} by {}", format, parsed.source.as_str()),
             )
-            .into()
-        })
-    })
-    .collect::<RResult<Vec<Option<String>>>>()
+        }),
+        Ok(None) | Err(DateFixError::NaImputed { .. }) => {
+            trace.record("result", || ("NA".into(), "NA imputed".into()))
+        }
+        Err(e) => trace.record("error", || {
+            (e.kind().to_string(), e.message().trim_end().to_string())
+        }),
+    }
+    trace_to_list(&trace)
+}
+
+/// Flag the dates in a column which match a missing-value token
+/// @noRd
+#[extendr]
+#[no_mangle]
+fn missing_dates(dates: Strings, na_strings: Option<Vec<String>>) -> Vec<bool> {
+    let missing = missing_tokens(na_strings);
+    dates
+        .iter()
+        .map(|date| missing.is_missing(column_date(date)))
+        .collect()
 }
 
 /// Main date fixing function - Rust implementation of .fix_date
 /// @noRd
 #[extendr]
 #[no_mangle]
+#[allow(clippy::too_many_arguments)]
 fn fix_date(
     date: Robj,
-    day_impute: Option<i32>,
-    month_impute: Option<i32>,
+    day_impute: Robj,
+    month_impute: Robj,
     subject: Option<String>,
     format: &str,
     excel: bool,
     roman_numeral: bool,
+    na_strings: Option<Vec<String>>,
 ) -> RResult<Option<String>> {
-    // Handle null/NA/empty dates at R object level
+    // Handle null/NA dates at R object level
     if date.is_null() || date.is_na() {
         return Ok(None);
     }
 
-    let date_str = if let Some(s) = date.as_str() {
-        if s.is_empty() || s == "NA" {
-            return Ok(None);
-        }
-        s
-    } else {
-        return Err(date_should_be_character().into());
+    // Blank dates and missing-value tokens are resolved by `fix_date_native`
+    let Some(date_str) = date.as_str() else {
+        return Err(DateFixError::NotCharacter.into());
     };
 
+    let options = ParseOptions::new()
+        .day_impute(imputation_from_robj(&day_impute)?)
+        .month_impute(imputation_from_robj(&month_impute)?)
+        .format(format_from_str(format)?)
+        .excel(excel)
+        .roman_numeral(roman_numeral)
+        .missing_tokens(missing_tokens(na_strings));
+
     // Use the common processing pipeline
-    process_date_pipeline(
-        date_str, 
-        day_impute, 
-        month_impute, 
-        subject.as_deref(), 
-        format, 
-        excel, 
-        roman_numeral
-    )
+    Ok(fix_date_native(date_str, &options, subject.as_deref())?)
 }
 
 // Macro to generate exports.
@@ -799,6 +878,14 @@ extendr_module! {
     fn checkday;
     fn fix_date;
     fn fix_date_column;
+    fn fix_date_details_column;
+    fn fix_date_cdisc_column;
+    fn date_precision_column;
+    fn date_interval_column;
+    fn fix_date_edtf_column;
+    fn ambiguous_dates_column;
+    fn explain_date_steps;
+    fn missing_dates;
 }
 
 #[cfg(test)]
@@ -841,569 +928,136 @@ mod tests {
     }
 
     #[test]
-    fn test_rm_ordinal_suffixes() {
-        assert_eq!(rm_ordinal_suffixes_optimized("1st January"), "1 January");
-        assert_eq!(rm_ordinal_suffixes_optimized("2nd February"), "2 February");
-        assert_eq!(rm_ordinal_suffixes_optimized("3rd March"), "3 March");
-        assert_eq!(rm_ordinal_suffixes_optimized("4th April"), "4 April");
-    }
-
-    #[test]
-    fn test_separate_date() {
-        assert_eq!(separate_date_optimized("01/02/2020"), vec!["01", "02", "2020"]);
-        assert_eq!(separate_date_optimized("01-02-2020"), vec!["01", "02", "2020"]);
-        assert_eq!(separate_date_optimized("01 02 2020"), vec!["01", "02", "2020"]);
-        assert_eq!(
-            separate_date_optimized("01 de febrero del 2020"),
-            vec!["01", "febrero", "2020"]
-        );
-    }
-
-    #[test]
-    fn test_convert_text_month() {
-        assert_eq!(convert_text_month_optimized("january 2020"), "01 2020");
-        assert_eq!(convert_text_month_optimized("février 2020"), "02 2020");
-        assert_eq!(convert_text_month_optimized("march 2020"), "03 2020");
-        assert_eq!(convert_text_month_optimized("декабрь 2020"), "12 2020");
-        // Test Spanish months
-        assert_eq!(convert_text_month_optimized("20 abril 1994"), "20 04 1994");
-        assert_eq!(convert_text_month_optimized("06 enero 2008"), "06 01 2008");
-    }
-
-    #[test]
-    fn test_year_prefix() {
-        // Assuming current year is 2025, years <= 25 should get 20 prefix, > 25 should get 19
-        assert_eq!(year_prefix("23"), "2023");
-        assert_eq!(year_prefix("99"), "1999");
-        assert_eq!(year_prefix("2020"), "2020"); // Already 4 digits
-    }
-
-    #[test]
-    fn test_is_numeric() {
-        assert!(is_numeric("12345"));
-        assert!(!is_numeric("123a5"));
-        assert!(!is_numeric("hello"));
-        assert!(is_numeric("0"));
-    }
-
-    #[test]
-    fn test_first_is_month() {
-        assert!(first_is_month(&vec![
-            "january".to_string(),
-            "1".to_string(),
-            "2020".to_string()
-        ]));
-        assert!(first_is_month(&vec![
-            "février".to_string(),
-            "1".to_string(),
-            "2020".to_string()
-        ]));
-        assert!(!first_is_month(&vec![
-            "1".to_string(),
-            "january".to_string(),
-            "2020".to_string()
-        ]));
-        assert!(!first_is_month(&vec![
-            "32".to_string(),
-            "1".to_string(),
-            "2020".to_string()
-        ]));
-    }
-
-    #[test]
-    fn test_roman_conversion() {
-        let date_vec = vec!["1".to_string(), "ii".to_string(), "2020".to_string()];
-        let result = roman_conversion(date_vec);
-        assert_eq!(result, vec!["1", "02", "2020"]);
-
-        let date_vec = vec!["15".to_string(), "xii".to_string(), "2020".to_string()];
-        let result = roman_conversion(date_vec);
-        assert_eq!(result, vec!["15", "12", "2020"]);
-    }
-
-    #[test]
-    fn test_check_output() {
-        // Valid date
-        let result = check_output(Some(15), Some(6), Some(2020)).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Invalid month
-        assert!(check_output(Some(15), Some(13), Some(2020)).is_err());
-
-        // Day adjustment for February in leap year
-        let result = check_output(Some(29), Some(2), Some(2020)).unwrap();
-        assert_eq!(result, (Some(29), Some(2), Some(2020)));
-
-        // Day adjustment for February in non-leap year
-        let result = check_output(Some(29), Some(2), Some(2021)).unwrap();
-        assert_eq!(result, (Some(28), Some(2), Some(2021)));
-    }
-
-    #[test]
-    fn test_spanish_date_parsing() {
-        // Test Spanish date separation
-        let spanish_date = "20 de abril de 1994";
-        let separated = separate_date_optimized(spanish_date);
-        println!("Separated: {:?}", separated);
-        assert_eq!(separated, vec!["20", "abril", "1994"]);
-
-        // Test month conversion on individual components
-        let mut components = vec!["20".to_string(), "abril".to_string(), "1994".to_string()];
-        for component in &mut components {
-            let converted = convert_text_month_optimized(component).into_owned();
-            *component = converted;
-        }
-        println!("After month conversion: {:?}", components);
-        assert_eq!(components, vec!["20", "04", "1994"]);
-    }
-
-    #[test]
-    fn test_handle_year_only_date() {
-        // Test valid year-only date with imputation
-        let result = handle_year_only_date("2020", Some(15), Some(6), false, false).unwrap();
-        assert_eq!(result, Some("2020-06-15".to_string()));
-
-        // Test year-only date with NA day imputation
-        let result = handle_year_only_date("2020", Some(-1), Some(6), true, false).unwrap();
-        assert_eq!(result, None);
-
-        // Test year-only date with NA month imputation
-        let result = handle_year_only_date("2020", Some(15), Some(-1), false, true).unwrap();
-        assert_eq!(result, None);
-
-        // Test year-only date with both NA imputation values
-        let result = handle_year_only_date("2020", Some(-1), Some(-1), true, true).unwrap();
-        assert_eq!(result, None);
-
-        // Test year-only date with missing month imputation (should error)
-        let result = handle_year_only_date("2020", Some(15), None, false, false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Missing month"));
-
-        // Test year-only date with missing day imputation (should error)
-        let result = handle_year_only_date("2020", None, Some(6), false, false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Missing day"));
-
-        // Test non-year-only date (should return None to continue processing)
-        let result = handle_year_only_date("01/02/2020", Some(15), Some(6), false, false).unwrap();
-        assert_eq!(result, None);
-
-        // Test non-numeric year-only (should return None to continue processing)
-        let result = handle_year_only_date("abcd", Some(15), Some(6), false, false).unwrap();
-        assert_eq!(result, None);
-
-        // Test 3-digit year (should return None to continue processing)
-        let result = handle_year_only_date("202", Some(15), Some(6), false, false).unwrap();
-        assert_eq!(result, None);
-
-        // Test 5-digit year (should return None to continue processing)
-        let result = handle_year_only_date("20201", Some(15), Some(6), false, false).unwrap();
-        assert_eq!(result, None);
-
-        // Test edge case: year 0000
-        let result = handle_year_only_date("0000", Some(1), Some(1), false, false).unwrap();
-        assert_eq!(result, Some("0000-01-01".to_string()));
-
-        // Test edge case: year 9999
-        let result = handle_year_only_date("9999", Some(31), Some(12), false, false).unwrap();
-        assert_eq!(result, Some("9999-12-31".to_string()));
-    }
-
-    #[test]
-    fn test_process_date_pipeline_na_imputation() {
-        // Test year-only date with NA month imputation
-        let result = process_date_pipeline(
-            "1994",
-            Some(1),      // day_impute = 1
-            Some(-1),     // month_impute = -1 (NA)
-            Some("1"),    // subject
-            "dmy",
-            false,
-            false,
-        ).unwrap();
-        assert_eq!(result, None); // Should return None for NA imputation
-
-        // Test year-only date with NA day imputation
-        let result = process_date_pipeline(
-            "1994",
-            Some(-1),     // day_impute = -1 (NA)
-            Some(7),      // month_impute = 7
-            Some("1"),    // subject
-            "dmy",
-            false,
-            false,
-        ).unwrap();
-        assert_eq!(result, None); // Should return None for NA imputation
-
-        // Test year-only date with both NA imputation values
-        let result = process_date_pipeline(
-            "1994",
-            Some(-1),     // day_impute = -1 (NA)
-            Some(-1),     // month_impute = -1 (NA)
-            Some("1"),    // subject
-            "dmy",
-            false,
-            false,
-        ).unwrap();
-        assert_eq!(result, None); // Should return None for NA imputation
-
-        // Test MM/YYYY format with NA day imputation
-        let result = process_date_pipeline(
-            "04/1994",
-            Some(-1),     // day_impute = -1 (NA)
-            Some(7),      // month_impute = 7
-            Some("1"),    // subject
-            "dmy",
-            false,
-            false,
-        ).unwrap();
-        assert_eq!(result, None); // Should return None for NA imputation
-    }
-
-    #[test]
-    fn test_handle_numeric_dates() {
-        // Test Excel date parsing
-        let result = handle_numeric_dates("44927", true).unwrap();
-        assert!(result.is_some());
-        let date_str = result.unwrap();
-        // Excel date 44927 should be around 2023 (give or take depending on Excel's epoch)
-        assert!(date_str.starts_with("202"));
-
-        // Test Unix timestamp parsing
-        let result = handle_numeric_dates("18628", false).unwrap();
-        assert!(result.is_some());
-        let date_str = result.unwrap();
-        // Unix day 18628 from 1970-01-01 should be around 2021
-        assert!(date_str.starts_with("202"));
-
-        // Test small Excel date
-        let result = handle_numeric_dates("1", true).unwrap();
-        assert!(result.is_some());
-        let date_str = result.unwrap();
-        assert_eq!(date_str, "1899-12-31"); // Excel day 1 = Dec 31, 1899
-
-        // Test small Unix date
-        let result = handle_numeric_dates("1", false).unwrap();
-        assert!(result.is_some());
-        let date_str = result.unwrap();
-        assert_eq!(date_str, "1970-01-02"); // Unix day 1 = Jan 2, 1970
-
-        // Test zero for both systems
-        let excel_zero = handle_numeric_dates("0", true).unwrap();
-        assert!(excel_zero.is_some());
-        let unix_zero = handle_numeric_dates("0", false).unwrap();
-        assert!(unix_zero.is_some());
-        // Excel day 0 vs Unix day 0 should be different
-        assert_ne!(excel_zero.unwrap(), unix_zero.unwrap());
-
-        // Test non-numeric input (should return None to continue processing)
-        let result = handle_numeric_dates("01/02/2020", true).unwrap();
-        assert_eq!(result, None);
-
-        // Test non-numeric input with letters
-        let result = handle_numeric_dates("abc123", true).unwrap();
-        assert_eq!(result, None);
-
-        // Test empty string
-        let result = handle_numeric_dates("", true).unwrap();
-        assert_eq!(result, None);
-
-        // Test mixed alphanumeric
-        let result = handle_numeric_dates("123abc", true).unwrap();
-        assert_eq!(result, None);
-
-        // Test with spaces
-        let result = handle_numeric_dates("123 456", true).unwrap();
-        assert_eq!(result, None);
-
-        // Test very large number that might overflow
-        let result = handle_numeric_dates("999999999999999999999999999999999", true);
-        // This should either return None (parsing fails) or handle gracefully
-        assert!(result.is_ok());
-        if let Ok(Some(_)) = result {
-            // If it parses, that's fine
-        } else {
-            // If it doesn't parse or returns None, that's also fine
-            assert!(true);
-        }
-
-        // Test negative numbers (should handle gracefully)
-        let result = handle_numeric_dates("-100", true);
-        assert!(result.is_ok()); // Should not panic, might return error or None
-    }
-
-    #[test]
-    fn test_parse_date_components() {
-        // Test DMY format (DD/MM/YYYY)
-        let date_vec = vec!["15".to_string(), "06".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Test MDY format (MM/DD/YYYY)
-        let date_vec = vec!["06".to_string(), "15".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "mdy", Some(1), false).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Test YMD format (YYYY/MM/DD)
-        let date_vec = vec!["2020".to_string(), "06".to_string(), "15".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Test MM/YYYY format with day imputation
-        let date_vec = vec!["06".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(15), false).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Test YYYY/MM format with day imputation
-        let date_vec = vec!["2020".to_string(), "06".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(15), false).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Test insufficient components (single element) - should error
-        let date_vec = vec!["2020".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(15), false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Insufficient"));
-
-        // Test missing day imputation (None) - should error
-        let date_vec = vec!["06".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", None, false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Missing day"));
-
-        // Test NA day imputation - should return special error
-        let date_vec = vec!["06".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(-1), true);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("NA imputation requested"));
-
-        // Test invalid format
-        let date_vec = vec!["15".to_string(), "06".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "xyz", Some(1), false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("format should be either"));
-
-        // Test overly long year (more than 4 digits) - should error
-        let date_vec = vec!["15".to_string(), "06".to_string(), "20201".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("unable to tidy"));
-
-        // Test invalid numeric components
-        let date_vec = vec!["abc".to_string(), "06".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Invalid"));
-
-        // Test ambiguous 2-component format (neither is 4 digits)
-        let date_vec = vec!["06".to_string(), "15".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Unable to determine"));
-
-        // Test MDY with day having trailing punctuation (should be cleaned)
-        let date_vec = vec!["06".to_string(), "15,".to_string(), "2020".to_string()];
-        let result = parse_date_components(&date_vec, "mdy", Some(1), false).unwrap();
-        assert_eq!(result, (Some(15), Some(6), Some(2020)));
-
-        // Test edge cases: year 0000
-        let date_vec = vec!["0000".to_string(), "01".to_string(), "01".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false).unwrap();
-        assert_eq!(result, (Some(1), Some(1), Some(0)));
-
-        // Test edge cases: year 9999
-        let date_vec = vec!["31".to_string(), "12".to_string(), "9999".to_string()];
-        let result = parse_date_components(&date_vec, "dmy", Some(1), false).unwrap();
-        assert_eq!(result, (Some(31), Some(12), Some(9999)));
-    }
-
-    #[test]
-    fn test_process_single_date_with_error_handling() {
+    fn test_fix_date_native() {
         // Test successful date processing
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "15/06/2020",
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2020-06-15".to_string()));
 
         // Test with subject
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "06/15/2020",
-            Some(1),
-            Some(1),
+            &ParseOptions::new().format(DateFormat::Mdy),
             Some("test_subject"),
-            "mdy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2020-06-15".to_string()));
 
         // Test critical errors that should be propagated - invalid format
-        // Use a date that won't be caught by fast-path parser to test format validation
-        let result = process_single_date_with_error_handling(
-            "15 06 2020", // Space-separated date that bypasses fast-path
-            Some(1),
-            Some(1),
-            None,
-            "xyz", // Invalid format
-            false,
-            false,
-        );
+        let result = format_from_str("xyz");
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("format should be either"));
+        assert!(result.unwrap_err().to_string().contains("format should be"));
 
         // Test critical errors that should be propagated - missing day imputation
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "06 2020", // 2-component space-separated date requiring day imputation
-            None, // No day imputation provided
-            Some(1),
+            &ParseOptions::new().day_impute(Imputation::Error), // No day imputation provided
             None,
-            "dmy",
-            false,
-            false,
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Missing day"));
 
         // Test critical errors that should be propagated - month out of range
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "15 13 2020", // Month 13 is invalid, space-separated
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Month not in expected range"));
 
         // Test critical errors that should be propagated - day out of range
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "32 06 2020", // Day 32 is invalid, space-separated
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Day not in expected range"));
 
         // Test critical errors that should be propagated - unable to tidy date
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "15 06 202001", // Year too long, space-separated
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("unable to tidy a date"));
 
         // Test empty date string (should return None, not error)
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "",
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, None);
 
         // Test NA date string (should return None, not error)
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "NA",
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, None);
 
         // Test partial date with imputation (MM/YYYY)
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "06/2020",
-            Some(25), // Day imputation
-            Some(1),
+            &ParseOptions::new().day_impute(Imputation::Value(25)), // Day imputation
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2020-06-25".to_string()));
 
         // Test date adjustment (Feb 30 -> Feb 28)
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "30/02/2021", // Feb 30 in non-leap year
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2021-02-28".to_string())); // Should be adjusted to Feb 28
 
         // Test date adjustment (Feb 29 in leap year - should be preserved)
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "29/02/2020", // Feb 29 in leap year
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2020-02-29".to_string())); // Should be preserved
 
         // Test with 2-digit year conversion
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "15/06/99", // 99 should become 1999
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("1999-06-15".to_string()));
 
         // Test Roman numeral conversion
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "15/xii/2020", // December in Roman numerals
-            Some(1),
-            Some(1),
+            &ParseOptions::new().roman_numeral(true), // Roman numeral mode
             None,
-            "dmy",
-            false,
-            true, // Roman numeral mode
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2020-12-15".to_string()));
 
         // Test date with month name (forces MDY)
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "january 15 2020",
-            Some(1),
-            Some(1),
+            &ParseOptions::new(), // Should be overridden to mdy due to month name
             None,
-            "dmy", // Should be overridden to mdy due to month name
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, Some("2020-01-15".to_string()));
     }
 
@@ -1411,39 +1065,23 @@ mod tests {
     fn test_process_single_date_error_propagation() {
         // Test that critical errors are properly propagated
         
-        // Invalid format error - use space-separated date that bypasses fast-path
-        let result = process_single_date_with_error_handling(
-            "15 06 2020",
-            Some(1),
-            Some(1),
-            None,
-            "invalid_format",
-            false,
-            false,
-        );
-        assert!(result.is_err());
+        // Invalid format error
+        assert!(format_from_str("invalid_format").is_err());
+        assert_eq!(format_from_str("auto").unwrap(), DateFormat::Auto);
         
         // Month out of range error - use space-separated date that bypasses fast-path
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "15 15 2020", // Month 15 is invalid
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
         );
         assert!(result.is_err());
         
         // Day out of range error - use space-separated date that bypasses fast-path
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "40 06 2020", // Day 40 is invalid
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
         );
         assert!(result.is_err());
     }
@@ -1453,50 +1091,203 @@ mod tests {
         // Test cases where errors should be converted to None instead of propagated
         
         // Empty string should return None, not error
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "",
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, None);
         
         // NA string should return None, not error
-        let result = process_single_date_with_error_handling(
+        let result = fix_date_native(
             "NA",
-            Some(1),
-            Some(1),
+            &ParseOptions::new(),
             None,
-            "dmy",
-            false,
-            false,
-        ).unwrap();
+        )
+        .unwrap();
         assert_eq!(result, None);
     }
 
     #[test]
-    fn test_validate_year_length() {
-        // Test valid 4-digit years
-        assert!(validate_year_length("2020").is_ok());
-        assert!(validate_year_length("1999").is_ok());
-        assert!(validate_year_length("0001").is_ok());
-        assert!(validate_year_length("9999").is_ok());
-        
-        // Test valid shorter years
-        assert!(validate_year_length("20").is_ok());
-        assert!(validate_year_length("1").is_ok());
-        assert!(validate_year_length("").is_ok()); // Empty is technically valid
-        
-        // Test invalid long years
-        assert!(validate_year_length("20201").is_err());
-        assert!(validate_year_length("123456").is_err());
-        
-        // Verify error message
-        let result = validate_year_length("20201");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("unable to tidy"));
+    fn test_diagnose_date() {
+        let diagnose = |date, day_impute| {
+            let result =
+                parse_date_pipeline(date, &ParseOptions::new().day_impute(day_impute));
+            diagnose_date(date, MissingTokens::defaults(), &result)
+        };
+
+        let (date, diag) = diagnose("15 06 2020", Imputation::Value(1));
+        assert_eq!(date.map(|d| d.to_iso_string()), Some("2020-06-15".to_string()));
+        assert_eq!(diag, RowDiagnostic::new(RowStatus::Ok));
+
+        let (date, diag) = diagnose("NA", Imputation::Value(1));
+        assert_eq!(date, None);
+        assert_eq!(diag.status, RowStatus::Missing);
+
+        let (date, diag) = diagnose("06 2020", Imputation::Na);
+        assert_eq!(date, None);
+        assert_eq!(diag.status, RowStatus::NaImputed);
+
+        let (date, diag) = diagnose("32/01/2023", Imputation::Value(1));
+        assert_eq!(date, None);
+        assert_eq!(diag.status, RowStatus::Error);
+        let error = diag.error.unwrap();
+        assert_eq!(error.kind(), "day_out_of_range");
+        assert_eq!(
+            error,
+            DateFixError::DayOutOfRange {
+                day: 32,
+                input: "32/01/2023".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_tokens() {
+        let defaults = MissingTokens::defaults();
+        for token in ["", "  ", "NA", "N/A", " unknown ", "UNK", "-", "Not Recorded", "Inconnu", "DESCONOCIDO"] {
+            assert!(defaults.is_missing(token), "{:?} should be missing", token);
+        }
+        assert!(!defaults.is_missing("2020-01-01"));
+        assert!(!defaults.is_missing("nov 2020"));
+
+        let custom = MissingTokens::new(["Pending ", "x"]);
+        assert!(custom.is_missing("pending"));
+        assert!(custom.is_missing("X"));
+        assert!(custom.is_missing(""));
+        assert!(!custom.is_missing("NA"));
+        // Lowercasing can shorten a token, as with the Kelvin sign
+        assert!(MissingTokens::new(["k"]).is_missing("\u{212A}"));
+
+        // User tokens replace the default set
+        assert_eq!(fix_date_native("N/A", &ParseOptions::new(), None), Ok(None));
+        let pending = ParseOptions::new().missing_tokens(custom.clone());
+        assert_eq!(fix_date_native("Pending", &pending, None), Ok(None));
+        assert!(fix_date_native("N/A", &pending, None).is_err());
+        let result = parse_date_pipeline("N/A", &ParseOptions::new());
+        let (date, diag) = diagnose_date("N/A", &custom, &result);
+        assert_eq!(date, None);
+        assert_eq!(diag.status, RowStatus::Error);
+    }
+
+    #[test]
+    fn test_map_column_preserves_order() {
+        let dates: Vec<String> = (0..PARALLEL_THRESHOLD * 2)
+            .map(|i| format!("{:02}/{:02}/{}", i % 28 + 1, i % 12 + 1, 1950 + i % 70))
+            .collect();
+        let parse = |i: usize| {
+            parse_date_pipeline(&dates[i], &ParseOptions::new())
+                .map(|p| p.map(|p| p.to_iso_string()))
+        };
+
+        let sequential = map_column(dates.len(), 1, parse);
+        let parallel = map_column(dates.len(), 4, parse);
+        assert_eq!(sequential, parallel);
+        assert_eq!(parallel[0], Ok(Some("1950-01-01".to_string())));
+
+        // The first failure in row order is reported, not whichever thread finishes first
+        let mut bad = dates.clone();
+        bad[PARALLEL_THRESHOLD + 7] = "32/01/2020".to_string();
+        bad[PARALLEL_THRESHOLD * 2 - 1] = "15 13 2020".to_string();
+        let results = map_column(bad.len(), 4, |i| {
+            parse_date_pipeline(&bad[i], &ParseOptions::new())
+        });
+        let first_error = results.into_iter().position(|r| r.is_err());
+        assert_eq!(first_error, Some(PARALLEL_THRESHOLD + 7));
+    }
+
+    #[test]
+    fn test_distinct_values() {
+        let dates = ["2020-01-01", "", "2020-01-01", "March 2021", "", "2020-01-01"];
+        let (distinct, rows) = distinct_values(&dates);
+        assert_eq!(distinct, vec!["2020-01-01", "", "March 2021"]);
+        assert_eq!(rows, vec![0, 1, 0, 2, 1, 0]);
+
+        let (distinct, rows) = distinct_values(&[]);
+        assert!(distinct.is_empty());
+        assert!(rows.is_empty());
+    }
+
+    #[test]
+    fn test_format_inference() {
+        let evidence = |date| {
+            let dmy = parse_date_pipeline(date, &ParseOptions::new().format(DateFormat::Dmy));
+            let mdy = parse_date_pipeline(date, &ParseOptions::new().format(DateFormat::Mdy));
+            OrderEvidence::from_results(&dmy, &mdy)
+        };
+        assert_eq!(evidence("25/12/2023"), OrderEvidence::DmyOnly);
+        assert_eq!(evidence("12/25/2023"), OrderEvidence::MdyOnly);
+        assert_eq!(evidence("03/04/2020"), OrderEvidence::Ambiguous);
+        assert_eq!(evidence("05/05/2020"), OrderEvidence::Neutral);
+        assert_eq!(evidence("2020-04-03"), OrderEvidence::Neutral);
+        assert_eq!(evidence("April 3 2020"), OrderEvidence::Neutral);
+        assert_eq!(evidence("40/40/2020"), OrderEvidence::Neutral);
+
+        let mut inference = FormatInference::default();
+        assert_eq!(inference.format(), "dmy");
+        inference.add(OrderEvidence::MdyOnly, 3);
+        inference.add(OrderEvidence::DmyOnly, 1);
+        inference.add(OrderEvidence::Ambiguous, 2);
+        assert_eq!(inference.format(), "mdy");
+        assert_eq!(inference.conflicts(), 1);
+
+        // Ambiguous rows follow the majority, unambiguous rows keep their reading
+        let dates = [
+            "12/25/2023",
+            "12/31/2023",
+            "25/12/2023",
+            "03/04/2020",
+            "12/31/2023",
+        ];
+        let (distinct, rows) = distinct_values(&dates);
+        let (results, inference) = parse_distinct(
+            &distinct,
+            &rows,
+            &ParseOptions::new().format(DateFormat::Auto),
+            1,
+        );
+        let inference = inference.unwrap();
+        assert_eq!(
+            (inference.mdy_only, inference.dmy_only, inference.ambiguous),
+            (3, 1, 1)
+        );
+        let iso: Vec<String> = rows
+            .iter()
+            .map(|&row| {
+                results[row]
+                    .as_ref()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .to_iso_string()
+            })
+            .collect();
+        assert_eq!(
+            iso,
+            [
+                "2023-12-25",
+                "2023-12-31",
+                "2023-12-25",
+                "2020-03-04",
+                "2023-12-31"
+            ]
+        );
+
+        let (_, inference) = parse_distinct(
+            &distinct,
+            &rows,
+            &ParseOptions::new().format(DateFormat::Dmy),
+            1,
+        );
+        assert_eq!(inference, None);
+    }
+
+    #[test]
+    fn test_error_mode_parse() {
+        assert_eq!(ErrorMode::parse("stop").unwrap(), ErrorMode::Stop);
+        assert_eq!(ErrorMode::parse("na").unwrap(), ErrorMode::Na);
+        assert_eq!(ErrorMode::parse("collect").unwrap(), ErrorMode::Collect);
+        assert!(ErrorMode::parse("ignore").is_err());
     }
 }
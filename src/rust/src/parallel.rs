@@ -0,0 +1,24 @@
+use rayon::prelude::*;
+
+/// Columns shorter than this are parsed on the calling thread, where starting
+/// a thread pool would cost more than it saves
+pub const PARALLEL_THRESHOLD: usize = 10_000;
+
+/// Apply `f` to every row index of a column, spreading large columns over
+/// `threads` threads
+///
+/// Results are returned in row order whatever the number of threads.
+pub fn map_column<T, F>(len: usize, threads: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Send + Sync,
+{
+    if threads <= 1 || len < PARALLEL_THRESHOLD {
+        return (0..len).map(f).collect();
+    }
+    match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+        Ok(pool) => pool.install(|| (0..len).into_par_iter().map(&f).collect()),
+        // Threads may be unavailable, for example under WebAssembly
+        Err(_) => (0..len).map(f).collect(),
+    }
+}